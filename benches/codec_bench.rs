@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate criterion;
+extern crate blockstack_lib;
+
+use blockstack_lib::chainstate::burn::BlockHeaderHash;
+use blockstack_lib::chainstate::stacks::index::TrieHash;
+use blockstack_lib::chainstate::stacks::{
+    CoinbasePayload, StacksBlock, StacksMicroblockHeader, StacksPrivateKey, StacksTransaction,
+    StacksTransactionSigner, StacksWorkScore, TokenTransferMemo, TransactionAuth,
+    TransactionPayload, TransactionVersion,
+};
+use blockstack_lib::net::StacksMessageCodec;
+use blockstack_lib::util::hash::Hash160;
+use blockstack_lib::util::vrf::VRFProof;
+use blockstack_lib::vm::Value;
+
+use criterion::Criterion;
+
+fn sign_tx(privk: &StacksPrivateKey, nonce: u64, payload: TransactionPayload) -> StacksTransaction {
+    let auth = TransactionAuth::from_p2pkh(privk).unwrap();
+    let mut tx = StacksTransaction::new(TransactionVersion::Testnet, auth, payload);
+    tx.chain_id = 0x80000000;
+    tx.set_tx_fee(1);
+    tx.set_origin_nonce(nonce);
+
+    let mut signer = StacksTransactionSigner::new(&tx);
+    signer.sign_origin(privk).unwrap();
+    signer.get_tx().unwrap()
+}
+
+/// Make a block with a mix of token transfers, contract-calls, and contract deployments, so
+/// decoding it exercises both many small items and a few large byte arrays.
+fn make_block(
+    num_transfers: u64,
+    num_contract_calls: u64,
+    num_contracts: u64,
+    contract_len: usize,
+) -> StacksBlock {
+    let privk = StacksPrivateKey::new();
+    let addr = TransactionAuth::from_p2pkh(&privk)
+        .unwrap()
+        .origin()
+        .address_testnet();
+
+    let mut txs = vec![sign_tx(
+        &privk,
+        0,
+        TransactionPayload::Coinbase(CoinbasePayload([0u8; 32])),
+    )];
+    let mut nonce = 1;
+    for _ in 0..num_transfers {
+        let payload = TransactionPayload::TokenTransfer(
+            addr.clone().into(),
+            123,
+            TokenTransferMemo([0u8; 34]),
+        );
+        txs.push(sign_tx(&privk, nonce, payload));
+        nonce += 1;
+    }
+    for _ in 0..num_contract_calls {
+        let payload = TransactionPayload::new_contract_call(
+            addr.clone(),
+            "hello-world",
+            "set-bar",
+            vec![Value::Int(1), Value::buff_from(vec![0x01; 32]).unwrap()],
+        )
+        .unwrap();
+        txs.push(sign_tx(&privk, nonce, payload));
+        nonce += 1;
+    }
+    for i in 0..num_contracts {
+        let body = format!(
+            "(define-data-var bar int 0) ;; {}",
+            "a".repeat(contract_len)
+        );
+        let payload =
+            TransactionPayload::new_smart_contract(&format!("contract-{}", i), &body).unwrap();
+        txs.push(sign_tx(&privk, nonce, payload));
+        nonce += 1;
+    }
+
+    let genesis = StacksBlock::genesis_block();
+    StacksBlock::from_parent(
+        &genesis.header,
+        &StacksMicroblockHeader::first_empty_unsigned(&BlockHeaderHash([0u8; 32])),
+        txs,
+        &StacksWorkScore::initial(),
+        &VRFProof::empty(),
+        &TrieHash([0u8; 32]),
+        &Hash160([0u8; 20]),
+    )
+}
+
+fn bench_block_decode(c: &mut Criterion) {
+    let block = make_block(1000, 500, 4, 100_000);
+    let bytes = block.serialize_to_vec();
+
+    c.bench_function("decode full block", |b| {
+        b.iter(|| StacksBlock::consensus_deserialize(&mut &bytes[..]).unwrap())
+    });
+}
+
+fn bench_bytes_decode(c: &mut Criterion) {
+    let bytes = vec![0x01u8; 1024 * 1024].serialize_to_vec();
+
+    c.bench_function("decode 1MB byte array", |b| {
+        b.iter(|| Vec::<u8>::consensus_deserialize(&mut &bytes[..]).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_block_decode, bench_bytes_decode);
+criterion_main!(benches);
@@ -171,10 +171,47 @@ impl BurnchainParameters {
 pub trait PublicKey: Clone + fmt::Debug + serde::Serialize + serde::de::DeserializeOwned {
     fn to_bytes(&self) -> Vec<u8>;
     fn verify(&self, data_hash: &[u8], sig: &MessageSignature) -> Result<bool, &'static str>;
+
+    /// Verify a batch of (public key, data hash, signature) triples.  Returns Ok(true) only if
+    /// every signature is valid; a signature that does not match its key makes the whole batch
+    /// Ok(false).  Malformed inputs are reported as errors, as with `verify`.
+    fn verify_batch(items: &[(Self, Vec<u8>, MessageSignature)]) -> Result<bool, &'static str> {
+        for (pubkey, data_hash, sig) in items.iter() {
+            if !pubkey.verify(data_hash, sig)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Verify an M-of-N multisig: check that at least `required` distinct keys in `pubkeys`
+    /// signed `data_hash`.  Each signature is matched against the keys that have not yet been
+    /// matched, so a signature (or a repeat of it) can vouch for at most one key.
+    fn verify_multisig(
+        pubkeys: &[Self],
+        sigs: &[MessageSignature],
+        data_hash: &[u8],
+        required: usize,
+    ) -> Result<bool, &'static str> {
+        let mut signed = vec![false; pubkeys.len()];
+        let mut num_signed = 0;
+        for sig in sigs.iter() {
+            for (i, pubkey) in pubkeys.iter().enumerate() {
+                if !signed[i] && pubkey.verify(data_hash, sig)? {
+                    signed[i] = true;
+                    num_signed += 1;
+                    break;
+                }
+            }
+        }
+        Ok(num_signed >= required)
+    }
 }
 
 pub trait PrivateKey: Clone + fmt::Debug + serde::Serialize + serde::de::DeserializeOwned {
     fn to_bytes(&self) -> Vec<u8>;
+    /// Sign a data hash.  Signing must be deterministic: signing the same hash with the same key
+    /// always yields the same signature, byte for byte, no matter which node does it.
     fn sign(&self, data_hash: &[u8]) -> Result<MessageSignature, &'static str>;
 }
 
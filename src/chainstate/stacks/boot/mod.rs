@@ -15,9 +15,9 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use chainstate::stacks::db::StacksChainState;
-use chainstate::stacks::Error;
 use chainstate::stacks::StacksAddress;
 use chainstate::stacks::StacksBlockHeader;
+use chainstate::stacks::{Error, PoxError};
 use vm::database::ClarityDatabase;
 
 use address::AddressHashMode;
@@ -323,7 +323,9 @@ impl StacksChainState {
     ) -> Result<Vec<(StacksAddress, u128)>, Error> {
         let reward_cycle = burnchain
             .block_height_to_reward_cycle(current_burn_height)
-            .ok_or(Error::PoxNoRewardCycle)?;
+            .ok_or(Error::PoxError(PoxError::NoRewardCycle {
+                burn_height: current_burn_height,
+            }))?;
 
         if !self.is_pox_active(sortdb, block_id, reward_cycle as u128)? {
             debug!(
@@ -288,10 +288,16 @@ impl StacksChainState {
 
         let mut snapshot = db.get_stx_balance_snapshot(principal);
         if snapshot.has_locked_tokens() {
-            return Err(Error::PoxAlreadyLocked);
+            return Err(Error::PoxError(PoxError::AlreadyLocked {
+                amount_locked: snapshot.balance().amount_locked,
+                unlock_height: snapshot.balance().unlock_height,
+            }));
         }
         if !snapshot.can_transfer(lock_amount) {
-            return Err(Error::PoxInsufficientBalance);
+            return Err(Error::PoxError(PoxError::InsufficientBalance {
+                required: lock_amount,
+                available: snapshot.get_available_balance(),
+            }));
         }
         snapshot.lock_tokens(lock_amount, unlock_burn_height);
 
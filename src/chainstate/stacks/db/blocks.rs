@@ -158,17 +158,20 @@ pub enum MemPoolRejection {
 }
 
 impl MemPoolRejection {
-    pub fn into_json(self, txid: &Txid) -> serde_json::Value {
+    /// The machine-readable reason for this rejection, and any data that explains it.
+    pub fn reason(&self) -> (&'static str, Option<serde_json::Value>) {
         use self::MemPoolRejection::*;
-        let (reason_code, reason_data) = match self {
-            SerializationFailure(e) => ("Serialization", Some(json!({"message": e.to_string()}))),
-            DeserializationFailure(e) => {
+        match *self {
+            SerializationFailure(ref e) => {
+                ("Serialization", Some(json!({"message": e.to_string()})))
+            }
+            DeserializationFailure(ref e) => {
                 ("Deserialization", Some(json!({"message": e.to_string()})))
             }
             TooMuchChaining {
                 max_nonce,
                 actual_nonce,
-                principal,
+                ref principal,
                 is_origin,
                 ..
             } => (
@@ -183,7 +186,7 @@ impl MemPoolRejection {
                 ),
             ),
             BadTransactionVersion => ("BadTransactionVersion", None),
-            FailedToValidate(e) => (
+            FailedToValidate(ref e) => (
                 "SignatureValidation",
                 Some(json!({"message": e.to_string()})),
             ),
@@ -193,7 +196,7 @@ impl MemPoolRejection {
                                                 "expected": expected,
                                                 "actual": actual})),
             ),
-            TransferRecipientIsSender(recipient) => (
+            TransferRecipientIsSender(ref recipient) => (
                 "TransferRecipientCannotEqualSender",
                 Some(json!({"recipient": recipient.to_string()})),
             ),
@@ -201,7 +204,7 @@ impl MemPoolRejection {
             BadNonces(TransactionNonceMismatch {
                 expected,
                 actual,
-                principal,
+                ref principal,
                 is_origin,
                 ..
             }) => (
@@ -221,12 +224,12 @@ impl MemPoolRejection {
             ),
             NoSuchContract => ("NoSuchContract", None),
             NoSuchPublicFunction => ("NoSuchPublicFunction", None),
-            BadFunctionArgument(e) => (
+            BadFunctionArgument(ref e) => (
                 "BadFunctionArgument",
                 Some(json!({"message": e.to_string()})),
             ),
             ConflictingNonceInMempool => ("ConflictingNonceInMempool", None),
-            ContractAlreadyExists(id) => (
+            ContractAlreadyExists(ref id) => (
                 "ContractAlreadyExists",
                 Some(json!({ "contract_identifier": id.to_string() })),
             ),
@@ -237,12 +240,16 @@ impl MemPoolRejection {
             NoCoinbaseViaMempool => ("NoCoinbaseViaMempool", None),
             // this should never happen via the RPC interface
             NoSuchChainTip(..) => ("ServerFailureNoSuchChainTip", None),
-            DBError(e) => (
+            DBError(ref e) => (
                 "ServerFailureDatabase",
                 Some(json!({"message": e.to_string()})),
             ),
-            Other(s) => ("ServerFailureOther", Some(json!({ "message": s }))),
-        };
+            Other(ref s) => ("ServerFailureOther", Some(json!({ "message": s }))),
+        }
+    }
+
+    pub fn into_json(self, txid: &Txid) -> serde_json::Value {
+        let (reason_code, reason_data) = self.reason();
         let mut result = json!({
             "txid": format!("{}", txid.to_hex()),
             "error": "transaction rejected",
@@ -258,6 +265,22 @@ impl MemPoolRejection {
     }
 }
 
+impl fmt::Display for MemPoolRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MemPoolRejection::Other(ref s) => fmt::Display::fmt(s, f),
+            _ => {
+                let (reason_code, reason_data) = self.reason();
+                write!(f, "{}", reason_code)?;
+                if let Some(reason_data) = reason_data {
+                    write!(f, ": {}", reason_data)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 impl From<db_error> for MemPoolRejection {
     fn from(e: db_error) -> MemPoolRejection {
         MemPoolRejection::DBError(e)
@@ -3379,25 +3402,25 @@ impl StacksChainState {
 
         let mut dup = microblock.clone();
         if let Err(e) = dup.verify(&pubkey_hash) {
-            let msg = format!(
-                "Invalid microblock {}: failed to verify signature with {}: {:?}",
+            let err = invalid_microblock!(
                 microblock.block_hash(),
+                "failed to verify signature with {}: {:?}",
                 pubkey_hash,
                 &e
             );
-            warn!("{}", &msg);
-            return Err(Error::InvalidStacksMicroblock(msg, microblock.block_hash()));
+            warn!("{}", &err);
+            return Err(err);
         }
 
         // static checks on transactions all pass
         let valid = microblock.validate_transactions_static(mainnet, chain_id);
         if !valid {
-            let msg = format!(
-                "Invalid microblock {}: one or more transactions failed static tests",
-                microblock.block_hash()
+            let err = invalid_microblock!(
+                microblock.block_hash(),
+                "one or more transactions failed static tests"
             );
-            warn!("{}", &msg);
-            return Err(Error::InvalidStacksMicroblock(msg, microblock.block_hash()));
+            warn!("{}", &err);
+            return Err(err);
         }
 
         // add to staging
@@ -4497,16 +4520,16 @@ impl StacksChainState {
 
             let root_hash = clarity_tx.get_root_hash();
             if root_hash != block.header.state_index_root {
-                let msg = format!(
-                    "Block {} state root mismatch: expected {}, got {}",
+                let err = invalid_block!(
                     block.block_hash(),
+                    "state root mismatch: expected {}, got {}",
                     root_hash,
                     block.header.state_index_root
                 );
-                warn!("{}", &msg);
+                warn!("{}", &err);
 
                 clarity_tx.rollback_block();
-                return Err(Error::InvalidStacksBlock(msg));
+                return Err(err);
             }
 
             debug!("Reached state root {}", root_hash);
@@ -5160,6 +5183,14 @@ impl StacksChainState {
                 }
                 Ok(())
             }
+            TransactionPayload::SmartContract(ref smart_contract) => {
+                // too big to be worth analyzing
+                StacksChainState::check_contract_src_size(
+                    smart_contract.code_body.len() as u64,
+                    MAX_CONTRACT_SRC_LEN as u64,
+                )
+                .map_err(MemPoolRejection::FailedToValidate)
+            }
             _ => Ok(()),
         }
     }
@@ -6115,6 +6146,51 @@ pub mod test {
         tx.commit().unwrap();
     }
 
+    #[test]
+    fn mempool_rejects_oversize_contract() {
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+
+        // pad the contract out with a comment, so it is otherwise valid
+        let contract = format!(
+            "(define-data-var bar int 0) ;; {}",
+            "a".repeat(MAX_CONTRACT_SRC_LEN as usize)
+        );
+        let tx_contract = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth.clone(),
+            TransactionPayload::new_smart_contract(&"hello-world".to_string(), &contract).unwrap(),
+        );
+
+        // the contract still fits in a transaction, so it survives decoding
+        assert!(contract.len() < MAX_TRANSACTION_LEN as usize);
+        let tx_bytes = tx_contract.serialize_to_vec();
+        let tx_contract = StacksTransaction::consensus_deserialize(&mut &tx_bytes[..]).unwrap();
+
+        match StacksChainState::can_admit_mempool_semantic(&tx_contract, false) {
+            Err(MemPoolRejection::FailedToValidate(chainstate_error::ContractTooLarge {
+                size,
+                max_size,
+            })) => {
+                assert_eq!(size, contract.len() as u64);
+                assert_eq!(max_size, MAX_CONTRACT_SRC_LEN as u64);
+            }
+            res => panic!("Expected ContractTooLarge, got {:?}", &res),
+        }
+
+        // the limit itself is allowed
+        let at_limit = "a".repeat(MAX_CONTRACT_SRC_LEN as usize);
+        let tx_contract = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth,
+            TransactionPayload::new_smart_contract(&"hello-world".to_string(), &at_limit).unwrap(),
+        );
+        StacksChainState::can_admit_mempool_semantic(&tx_contract, false).unwrap();
+    }
+
     #[test]
     fn stacks_db_block_load_store_empty() {
         let chainstate =
@@ -37,23 +37,129 @@ use chainstate::burn::db::sortdb::*;
 
 use net::Error as net_error;
 
-use vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+use chainstate::stacks::boot::{boot_code_id, BOOT_CODE_COSTS, BOOT_CODE_COST_VOTING_TESTNET};
+
+use vm::types::{
+    FunctionSignature, FunctionType, PrincipalData, QualifiedContractIdentifier,
+    StandardPrincipalData, TraitIdentifier,
+};
 
 use vm::contexts::{AssetMap, OwnedEnvironment};
+use vm::costs::{ExecutionCost, LimitedCostTracker};
 
+use vm::analysis::contract_interface_builder::{
+    build_contract_interface, ContractInterface, ContractInterfaceFunction,
+    ContractInterfaceFunctionAccess, ContractInterfaceFungibleTokens, ContractInterfaceMap,
+    ContractInterfaceNonFungibleTokens, ContractInterfaceVariable,
+};
 use vm::analysis::run_analysis;
+use vm::analysis::type_checker::TypeChecker;
+use vm::analysis::ContractAnalysis;
 use vm::ast::build_ast;
+use vm::representations::SymbolicExpression;
 use vm::types::{AssetIdentifier, Value};
 
 use vm::clarity::ClarityConnection;
 
-pub use vm::analysis::errors::CheckErrors;
+pub use vm::analysis::errors::{CheckError, CheckErrors};
 use vm::errors::Error as clarity_vm_error;
 
-use vm::database::ClarityDatabase;
+use vm::database::{BurnStateDB, ClarityDatabase, MemoryBackingStore};
 
 use vm::contracts::Contract;
 
+/// Items of one kind (functions, maps, ...) that were added, removed, or changed between two
+/// versions of a contract's interface.  Items are matched up by name.  Changed items are
+/// reported as (old, new) pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceItemDiff<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub changed: Vec<(T, T)>,
+}
+
+/// Difference between the interface of a deployed contract and a proposed replacement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceDiff {
+    pub functions: InterfaceItemDiff<ContractInterfaceFunction>,
+    pub variables: InterfaceItemDiff<ContractInterfaceVariable>,
+    pub maps: InterfaceItemDiff<ContractInterfaceMap>,
+    pub fungible_tokens: InterfaceItemDiff<ContractInterfaceFungibleTokens>,
+    pub non_fungible_tokens: InterfaceItemDiff<ContractInterfaceNonFungibleTokens>,
+}
+
+impl<T: Clone + PartialEq> InterfaceItemDiff<T> {
+    fn from_items<F: Fn(&T) -> &str>(old: &[T], new: &[T], name: F) -> InterfaceItemDiff<T> {
+        let mut diff = InterfaceItemDiff {
+            added: vec![],
+            removed: vec![],
+            changed: vec![],
+        };
+        for old_item in old.iter() {
+            match new.iter().find(|new_item| name(new_item) == name(old_item)) {
+                Some(new_item) => {
+                    if new_item != old_item {
+                        diff.changed.push((old_item.clone(), new_item.clone()));
+                    }
+                }
+                None => diff.removed.push(old_item.clone()),
+            }
+        }
+        for new_item in new.iter() {
+            if !old.iter().any(|old_item| name(old_item) == name(new_item)) {
+                diff.added.push(new_item.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl InterfaceDiff {
+    fn from_interfaces(old: &ContractInterface, new: &ContractInterface) -> InterfaceDiff {
+        InterfaceDiff {
+            functions: InterfaceItemDiff::from_items(&old.functions, &new.functions, |f| &f.name),
+            variables: InterfaceItemDiff::from_items(&old.variables, &new.variables, |v| &v.name),
+            maps: InterfaceItemDiff::from_items(&old.maps, &new.maps, |m| &m.name),
+            fungible_tokens: InterfaceItemDiff::from_items(
+                &old.fungible_tokens,
+                &new.fungible_tokens,
+                |t| &t.name,
+            ),
+            non_fungible_tokens: InterfaceItemDiff::from_items(
+                &old.non_fungible_tokens,
+                &new.non_fungible_tokens,
+                |t| &t.name,
+            ),
+        }
+    }
+
+    /// Would replacing the old contract with the new one break existing callers?  Adding items
+    /// is never breaking, and neither is touching private functions.  Removing or changing any
+    /// public or read-only function, or any data definition, is.
+    pub fn is_breaking(&self) -> bool {
+        let is_callable =
+            |f: &ContractInterfaceFunction| f.access != ContractInterfaceFunctionAccess::private;
+        self.functions.removed.iter().any(&is_callable)
+            || self
+                .functions
+                .changed
+                .iter()
+                .any(|(old, new)| is_callable(old) || is_callable(new))
+            || !self.variables.removed.is_empty()
+            || !self.variables.changed.is_empty()
+            || !self.maps.removed.is_empty()
+            || !self.maps.changed.is_empty()
+            || !self.fungible_tokens.removed.is_empty()
+            || !self.fungible_tokens.changed.is_empty()
+            || !self.non_fungible_tokens.removed.is_empty()
+            || !self.non_fungible_tokens.changed.is_empty()
+    }
+}
+
 impl StacksChainState {
     pub fn get_contract<T: ClarityConnection>(
         clarity_tx: &mut T,
@@ -68,6 +174,287 @@ impl StacksChainState {
             .map_err(Error::ClarityError)
     }
 
+    /// Get a contract as of the block `at_block`, which must be an ancestor of (or equal to) the
+    /// block `clarity_tx` is positioned at.  This uses the same time-shifting as `(at-block ...)`.
+    /// Returns Ok(None) if the contract did not exist at that block.
+    pub fn get_contract_at_block<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+        at_block: &StacksBlockId,
+    ) -> Result<Option<Contract>, Error> {
+        clarity_tx
+            .with_clarity_db_readonly(|ref mut db| {
+                let prior_tip = db
+                    .set_block_hash(at_block.clone(), false)
+                    .map_err(clarity_error::Interpreter)?;
+                let result = match db.get_contract(contract_id) {
+                    Ok(c) => Ok(Some(c)),
+                    Err(clarity_vm_error::Unchecked(CheckErrors::NoSuchContract(_))) => Ok(None),
+                    Err(e) => Err(clarity_error::Interpreter(e)),
+                };
+                db.set_block_hash(prior_tip, true)
+                    .expect("FATAL: failed to restore prior active block after reading a contract");
+                result
+            })
+            .map_err(Error::ClarityError)
+    }
+
+    /// Does the contract exist?  This only probes for the contract's metadata, so it is cheaper
+    /// than `get_contract()`.
+    pub fn contract_exists<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<bool, Error> {
+        Ok(clarity_tx.with_clarity_db_readonly(|ref mut db| db.has_contract(contract_id)))
+    }
+
+    /// Refuse to deploy a contract whose source is `size` bytes, if that is bigger than
+    /// `max_size` bytes.  Checking this before the contract is parsed avoids wasting effort on
+    /// analyzing it.
+    pub fn check_contract_src_size(size: u64, max_size: u64) -> Result<(), Error> {
+        if size > max_size {
+            return Err(Error::ContractTooLarge { size, max_size });
+        }
+        Ok(())
+    }
+
+    /// Deploying a contract that already exists can fail in a different way at each step: the
+    /// analysis DB reports a `CheckErrors::ContractAlreadyExists`, the Clarity VM reports it as
+    /// an unchecked error, and the underlying DB reports an `ExistsError`.  Map all of these to
+    /// `Error::ContractAlreadyExists`, so callers only need to look for one error.  All other
+    /// errors are returned unchanged.
+    pub fn normalize_contract_deploy_error(
+        contract_id: &QualifiedContractIdentifier,
+        err: Error,
+    ) -> Error {
+        match err {
+            Error::DBError(db_error::ExistsError)
+            | Error::ClarityError(clarity_error::Interpreter(clarity_vm_error::Unchecked(
+                CheckErrors::ContractAlreadyExists(_),
+            ))) => Error::ContractAlreadyExists(contract_id.clone()),
+            Error::ClarityError(clarity_error::Analysis(ref check_error))
+                if match check_error.err {
+                    CheckErrors::ContractAlreadyExists(_) => true,
+                    _ => false,
+                } =>
+            {
+                Error::ContractAlreadyExists(contract_id.clone())
+            }
+            e => e,
+        }
+    }
+
+    /// Get the length of a contract's source code, without loading the contract.
+    /// Returns Ok(None) if the contract does not exist.
+    pub fn get_contract_size<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<Option<u64>, Error> {
+        clarity_tx
+            .with_clarity_db_readonly(|ref mut db| match db.get_contract_src_size(contract_id) {
+                Ok(size) => Ok(Some(size)),
+                Err(clarity_vm_error::Unchecked(CheckErrors::NoSuchContract(_))) => Ok(None),
+                Err(e) => Err(clarity_error::Interpreter(e)),
+            })
+            .map_err(Error::ClarityError)
+    }
+
+    /// Get the Clarity source code of a contract, exactly as it was deployed.
+    /// Returns Ok(None) if the contract does not exist.
+    pub fn get_contract_src<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<Option<String>, Error> {
+        // a missing contract's metadata reads as NoSuchContract, which get_contract_src()
+        // reports as None
+        Ok(clarity_tx.with_clarity_db_readonly(|ref mut db| db.get_contract_src(contract_id)))
+    }
+
+    /// Get a contract, preferring the unconfirmed (microblock) state if `prefer_unconfirmed` is
+    /// set and the unconfirmed state is readable and has the contract.  Otherwise, the contract
+    /// is read from the confirmed state at `confirmed_tip`.
+    /// Returns Ok(None) if the contract could not be found.
+    pub fn get_contract_latest(
+        &mut self,
+        burn_dbconn: &dyn BurnStateDB,
+        confirmed_tip: &StacksBlockId,
+        contract_id: &QualifiedContractIdentifier,
+        prefer_unconfirmed: bool,
+    ) -> Result<Option<Contract>, Error> {
+        if prefer_unconfirmed {
+            let unconfirmed_contract = self
+                .with_read_only_unconfirmed_clarity_tx(burn_dbconn, |clarity_tx| {
+                    StacksChainState::get_contract(clarity_tx, contract_id)
+                })?
+                .transpose()?
+                .flatten();
+
+            if unconfirmed_contract.is_some() {
+                return Ok(unconfirmed_contract);
+            }
+        }
+
+        let confirmed_contract = self
+            .with_read_only_clarity_tx(burn_dbconn, confirmed_tip, |clarity_tx| {
+                StacksChainState::get_contract(clarity_tx, contract_id)
+            })
+            .transpose()?
+            .flatten();
+
+        Ok(confirmed_contract)
+    }
+
+    /// Check whether the contract `contract_id` structurally conforms to the trait `trait_id` --
+    /// i.e. it defines every function in the trait as public or read-only, with compatible
+    /// argument and return types.  Both the contract's analysis and the trait's defining
+    /// contract's analysis must be present.
+    /// Returns Ok(false) if the contract does not conform to the trait.
+    pub fn contract_conforms_to_trait<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+        trait_id: &TraitIdentifier,
+    ) -> Result<bool, Error> {
+        clarity_tx
+            .with_analysis_db_readonly(|db| {
+                let contract_analysis = db
+                    .load_contract(contract_id)
+                    .ok_or(CheckErrors::NoSuchContract(contract_id.to_string()))?;
+                let trait_definition = db
+                    .get_defined_trait(&trait_id.contract_identifier, &trait_id.name)?
+                    .ok_or(CheckErrors::TraitReferenceUnknown(
+                        trait_id.name.to_string(),
+                    ))?;
+
+                match contract_analysis.check_trait_compliance(trait_id, &trait_definition) {
+                    Ok(()) => Ok(true),
+                    Err(CheckError {
+                        err: CheckErrors::BadTraitImplementation(..),
+                        ..
+                    }) => Ok(false),
+                    Err(e) => Err(e),
+                }
+            })
+            .map_err(|e| Error::ClarityError(clarity_error::Analysis(e)))
+    }
+
+    /// Compare the interface of the deployed contract `old_id` with that of `new_source`, as if
+    /// it were deployed as `new_id`.  The new source is analyzed against the current chain state
+    /// (so it may refer to deployed contracts), but nothing is stored.
+    pub fn diff_contract_interfaces<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        old_id: &QualifiedContractIdentifier,
+        new_source: &str,
+        new_id: &QualifiedContractIdentifier,
+    ) -> Result<InterfaceDiff, Error> {
+        let mut expressions = build_ast(new_id, new_source, &mut LimitedCostTracker::new_free())
+            .map_err(|e| Error::ClarityError(clarity_error::Parse(e)))?
+            .expressions;
+
+        clarity_tx
+            .with_analysis_db_readonly(|db| {
+                let old_analysis = db
+                    .load_contract(old_id)
+                    .ok_or(CheckErrors::NoSuchContract(old_id.to_string()))?;
+                let new_analysis = run_analysis(
+                    new_id,
+                    &mut expressions,
+                    db,
+                    false,
+                    LimitedCostTracker::new_free(),
+                )
+                .map_err(|(e, _)| e)?;
+
+                Ok(InterfaceDiff::from_interfaces(
+                    &build_contract_interface(&old_analysis),
+                    &build_contract_interface(&new_analysis),
+                ))
+            })
+            .map_err(|e| Error::ClarityError(clarity_error::Analysis(e)))
+    }
+
+    /// Break down the analysis cost of a contract by top-level expression.  The contract is
+    /// analyzed in a scratch, in-memory Clarity database that holds only the default boot cost
+    /// contracts, so the costs reported here are the ones the default cost functions compute.
+    /// Each entry is the name of the defined item (or the expression's text, if it is not a
+    /// definition) and the additional analysis cost that expression incurs.  Expressions are
+    /// reported in the order in which analysis visits them.
+    pub fn analyze_contract_cost_breakdown(
+        source: &str,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<Vec<(String, ExecutionCost)>, Error> {
+        let mut store = MemoryBackingStore::new();
+        {
+            let mut env = OwnedEnvironment::new_free(false, store.as_clarity_db());
+            for (name, code) in [
+                ("costs", BOOT_CODE_COSTS),
+                ("cost-voting", BOOT_CODE_COST_VOTING_TESTNET.as_str()),
+            ]
+            .iter()
+            {
+                env.initialize_contract(boot_code_id(name, false), code)
+                    .map_err(|e| Error::ClarityError(clarity_error::Interpreter(e)))?;
+            }
+        }
+
+        let cost_tracker = LimitedCostTracker::new_mid_block(
+            false,
+            ExecutionCost::max_value(),
+            &mut store.as_clarity_db(),
+        )
+        .map_err(|e| Error::ClarityError(clarity_error::Interpreter(e.into())))?;
+
+        let mut expressions = build_ast(contract_id, source, &mut LimitedCostTracker::new_free())
+            .map_err(|e| Error::ClarityError(clarity_error::Parse(e)))?
+            .expressions;
+
+        // analyze the whole contract first, so that every analysis pass gets to report errors
+        run_analysis(
+            contract_id,
+            &mut expressions,
+            &mut store.as_analysis_db(),
+            false,
+            cost_tracker.clone(),
+        )
+        .map_err(|(e, _)| Error::ClarityError(clarity_error::Analysis(e)))?;
+
+        // only type checking incurs analysis costs, so a single type checking pass yields the
+        // cost of each (dependency-sorted) expression.
+        let mut contract_analysis =
+            ContractAnalysis::new(contract_id.clone(), expressions.clone(), cost_tracker);
+        let expression_costs = store
+            .as_analysis_db()
+            .execute(|db| TypeChecker::run_pass_with_expression_costs(&mut contract_analysis, db))
+            .map_err(|e| Error::ClarityError(clarity_error::Analysis(e)))?;
+
+        Ok(expressions
+            .iter()
+            .map(StacksChainState::top_level_expression_name)
+            .zip(expression_costs)
+            .collect())
+    }
+
+    /// Name a top-level expression -- i.e. the name of the thing it defines, or the text of the
+    /// expression itself if it is not a definition.
+    fn top_level_expression_name(expr: &SymbolicExpression) -> String {
+        if let Some(list) = expr.match_list() {
+            if let (Some(define), Some(signature)) = (list.first(), list.get(1)) {
+                if define
+                    .match_atom()
+                    .is_some_and(|a| a.starts_with("define-"))
+                {
+                    let name = match signature.match_list() {
+                        Some(sig) => sig.first().and_then(|n| n.match_atom()),
+                        None => signature.match_atom(),
+                    };
+                    if let Some(name) = name {
+                        return name.to_string();
+                    }
+                }
+            }
+        }
+        expr.to_string()
+    }
+
     pub fn get_data_var<T: ClarityConnection>(
         clarity_tx: &mut T,
         contract_id: &QualifiedContractIdentifier,
@@ -85,4 +472,748 @@ impl StacksChainState {
             })
             .map_err(Error::ClarityError)
     }
+
+    /// Look up the entry for `key` in the contract's data map `map_name`.
+    /// Returns Ok(None) if the map has no such entry, and an error if the map itself does not
+    /// exist.
+    pub fn get_map_entry<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+        map_name: &str,
+        key: &Value,
+    ) -> Result<Option<Value>, Error> {
+        clarity_tx
+            .with_clarity_db_readonly(|ref mut db| {
+                db.fetch_entry_unknown_descriptor(contract_id, map_name, key)
+                    .map(|entry| entry.expect_optional())
+                    .map_err(clarity_error::Interpreter)
+            })
+            .map_err(Error::ClarityError)
+    }
+
+    /// Look up several data variables of one contract at once, in a single read-only pass over
+    /// the Clarity database.  Each name is paired with its value, or None if the contract has no
+    /// such data variable.  Results are in the same order as `names`.
+    pub fn get_data_vars<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+        names: &[&str],
+    ) -> Result<Vec<(String, Option<Value>)>, Error> {
+        clarity_tx
+            .with_clarity_db_readonly(|ref mut db| {
+                let mut values = Vec::with_capacity(names.len());
+                for name in names.iter() {
+                    let value = match db.lookup_variable_unknown_descriptor(contract_id, name) {
+                        Ok(c) => Some(c),
+                        Err(clarity_vm_error::Unchecked(CheckErrors::NoSuchDataVariable(_))) => {
+                            None
+                        }
+                        Err(e) => return Err(clarity_error::Interpreter(e)),
+                    };
+                    values.push((name.to_string(), value));
+                }
+                Ok(values)
+            })
+            .map_err(Error::ClarityError)
+    }
+
+    /// List the names of the data variables declared by a contract (in sorted order), as
+    /// recorded in its analysis.
+    pub fn list_data_vars<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<Vec<String>, Error> {
+        clarity_tx
+            .with_analysis_db_readonly(|db| {
+                let contract_analysis = db
+                    .load_contract(contract_id)
+                    .ok_or(CheckErrors::NoSuchContract(contract_id.to_string()))?;
+                Ok(contract_analysis
+                    .persisted_variable_types
+                    .keys()
+                    .map(|name| name.to_string())
+                    .collect())
+            })
+            .map_err(|e| Error::ClarityError(clarity_error::Analysis(e)))
+    }
+
+    /// Get the argument types and return type of a contract's public function, as recorded in
+    /// its analysis.
+    /// Returns Ok(None) if the contract has no such public function, and a `NoSuchContract`
+    /// analysis error if the contract does not exist.
+    pub fn get_public_function_signature<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+        func_name: &str,
+    ) -> Result<Option<FunctionSignature>, Error> {
+        clarity_tx
+            .with_analysis_db_readonly(|db| {
+                db.get_public_function_type(contract_id, func_name)
+                    .map(|function_type| match function_type {
+                        // user-defined functions always have fixed signatures
+                        Some(FunctionType::Fixed(function)) => Some(function.into()),
+                        _ => None,
+                    })
+            })
+            .map_err(|e| Error::ClarityError(clarity_error::Analysis(e)))
+    }
+
+    /// Load the analysis that was stored when the contract was deployed, so callers can inspect
+    /// its function signatures without re-running the analysis.  The stored analysis does not
+    /// include the type map or the contract's expressions.
+    /// Returns Ok(None) if the contract does not exist.
+    pub fn get_contract_analysis<T: ClarityConnection>(
+        clarity_tx: &mut T,
+        contract_id: &QualifiedContractIdentifier,
+    ) -> Result<Option<ContractAnalysis>, Error> {
+        Ok(clarity_tx.with_analysis_db_readonly(|db| db.load_contract(contract_id)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chainstate::stacks::index::MarfTrieId;
+    use vm::clarity::ClarityInstance;
+    use vm::costs::ExecutionCost;
+    use vm::database::{MarfedKV, NULL_BURN_STATE_DB, NULL_HEADER_DB};
+    use vm::types::{FixedFunction, FunctionType, TypeSignature};
+
+    /// Make a Clarity instance whose genesis block, StacksBlockId([0; 32]), is committed.
+    fn new_test_instance() -> ClarityInstance {
+        let mut clarity_instance =
+            ClarityInstance::new(false, MarfedKV::temporary(), ExecutionCost::max_value());
+
+        clarity_instance
+            .begin_test_genesis_block(
+                &StacksBlockId::sentinel(),
+                &StacksBlockId([0 as u8; 32]),
+                &NULL_HEADER_DB,
+                &NULL_BURN_STATE_DB,
+            )
+            .commit_block();
+
+        clarity_instance
+    }
+
+    /// Begin block StacksBlockId([1; 32]) on top of the genesis block of `new_test_instance()`.
+    fn begin_test_block<'a>(
+        clarity_instance: &'a mut ClarityInstance,
+    ) -> ClarityBlockConnection<'a> {
+        clarity_instance.begin_block(
+            &StacksBlockId([0 as u8; 32]),
+            &StacksBlockId([1 as u8; 32]),
+            &NULL_HEADER_DB,
+            &NULL_BURN_STATE_DB,
+        )
+    }
+
+    fn deploy_contract(
+        clarity_tx: &mut ClarityBlockConnection,
+        contract_id: &QualifiedContractIdentifier,
+        contract: &str,
+    ) {
+        clarity_tx.as_transaction(|tx| {
+            let (ast, analysis) = tx.analyze_smart_contract(contract_id, contract).unwrap();
+            tx.initialize_smart_contract(contract_id, &ast, contract, |_, _| false)
+                .unwrap();
+            tx.save_analysis(contract_id, &analysis).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_analyze_contract_cost_breakdown() {
+        let contract_id = QualifiedContractIdentifier::local("breakdown").unwrap();
+        let contract = "
+            (define-constant ten 10)
+            (define-read-only (cheap) (ok u1))
+            (define-read-only (expensive (x int))
+                (let ((a (+ x 1)) (b (* x 2)) (c (- x 3)))
+                    (if (> a b)
+                        (ok (list a b c (+ a b c) (* a b c) (- a b c)))
+                        (ok (list c b a (+ c b a) (* c b a) (- c b a))))))";
+
+        let breakdown =
+            StacksChainState::analyze_contract_cost_breakdown(contract, &contract_id).unwrap();
+        assert_eq!(breakdown.len(), 3);
+
+        let cost_of = |name: &str| {
+            breakdown
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, cost)| cost.clone())
+                .unwrap()
+        };
+        assert!(cost_of("expensive").runtime > cost_of("cheap").runtime);
+        assert!(cost_of("ten").runtime > 0);
+
+        // analysis errors are reported
+        match StacksChainState::analyze_contract_cost_breakdown(
+            "(define-read-only (bad) (+ 1 u1))",
+            &contract_id,
+        ) {
+            Err(Error::ClarityError(clarity_error::Analysis(_))) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn test_diff_contract_interfaces() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let old_id = QualifiedContractIdentifier::local("counter").unwrap();
+        let new_id = QualifiedContractIdentifier::local("counter-v2").unwrap();
+        deploy_contract(
+            &mut conn,
+            &old_id,
+            "(define-data-var count int 0)
+             (define-private (helper) (var-get count))
+             (define-public (incr) (ok (var-set count (+ 1 (var-get count)))))
+             (define-read-only (get-count) (helper))",
+        );
+
+        // added function: not breaking
+        let diff = StacksChainState::diff_contract_interfaces(
+            &mut conn,
+            &old_id,
+            "(define-data-var count int 0)
+             (define-private (helper) (var-get count))
+             (define-public (incr) (ok (var-set count (+ 1 (var-get count)))))
+             (define-read-only (get-count) (helper))
+             (define-public (decr) (ok (var-set count (- (var-get count) 1))))",
+            &new_id,
+        )
+        .unwrap();
+        assert_eq!(diff.functions.added.len(), 1);
+        assert_eq!(diff.functions.added[0].name, "decr");
+        assert!(diff.functions.removed.is_empty());
+        assert!(diff.functions.changed.is_empty());
+        assert!(diff.variables.is_empty());
+        assert!(!diff.is_breaking());
+
+        // removed function: breaking
+        let diff = StacksChainState::diff_contract_interfaces(
+            &mut conn,
+            &old_id,
+            "(define-data-var count int 0)
+             (define-private (helper) (var-get count))
+             (define-read-only (get-count) (helper))",
+            &new_id,
+        )
+        .unwrap();
+        assert!(diff.functions.added.is_empty());
+        assert_eq!(diff.functions.removed.len(), 1);
+        assert_eq!(diff.functions.removed[0].name, "incr");
+        assert!(diff.is_breaking());
+
+        // changed signature: breaking
+        let diff = StacksChainState::diff_contract_interfaces(
+            &mut conn,
+            &old_id,
+            "(define-data-var count int 0)
+             (define-private (helper) (var-get count))
+             (define-public (incr (by int)) (ok (var-set count (+ by (var-get count)))))
+             (define-read-only (get-count) (helper))",
+            &new_id,
+        )
+        .unwrap();
+        assert_eq!(diff.functions.changed.len(), 1);
+        assert_eq!(diff.functions.changed[0].0.name, "incr");
+        assert_eq!(diff.functions.changed[0].0.args.len(), 0);
+        assert_eq!(diff.functions.changed[0].1.args.len(), 1);
+        assert!(diff.is_breaking());
+
+        // changed private function: not breaking
+        let diff = StacksChainState::diff_contract_interfaces(
+            &mut conn,
+            &old_id,
+            "(define-data-var count int 0)
+             (define-private (helper (x int)) (+ x (var-get count)))
+             (define-public (incr) (ok (var-set count (+ 1 (var-get count)))))
+             (define-read-only (get-count) (helper 0))",
+            &new_id,
+        )
+        .unwrap();
+        assert_eq!(diff.functions.changed.len(), 1);
+        assert!(!diff.is_breaking());
+
+        // missing old contract
+        match StacksChainState::diff_contract_interfaces(
+            &mut conn,
+            &QualifiedContractIdentifier::local("missing").unwrap(),
+            "(define-read-only (foo) 1)",
+            &new_id,
+        ) {
+            Err(Error::ClarityError(clarity_error::Analysis(CheckError {
+                err: CheckErrors::NoSuchContract(_),
+                ..
+            }))) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_contract_exists() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("exists").unwrap();
+        deploy_contract(&mut conn, &contract_id, "(define-read-only (foo) 1)");
+
+        assert!(StacksChainState::contract_exists(&mut conn, &contract_id).unwrap());
+        assert!(!StacksChainState::contract_exists(
+            &mut conn,
+            &QualifiedContractIdentifier::local("missing").unwrap()
+        )
+        .unwrap());
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_contract_at_block() {
+        let mut clarity_instance = new_test_instance();
+
+        let contract_id = QualifiedContractIdentifier::local("historical").unwrap();
+
+        // deployed at height 1
+        let mut conn = begin_test_block(&mut clarity_instance);
+        deploy_contract(&mut conn, &contract_id, "(define-read-only (foo) 1)");
+        conn.commit_block();
+
+        let mut conn = clarity_instance.begin_block(
+            &StacksBlockId([1 as u8; 32]),
+            &StacksBlockId([2 as u8; 32]),
+            &NULL_HEADER_DB,
+            &NULL_BURN_STATE_DB,
+        );
+
+        assert!(StacksChainState::get_contract_at_block(
+            &mut conn,
+            &contract_id,
+            &StacksBlockId([0 as u8; 32])
+        )
+        .unwrap()
+        .is_none());
+
+        let contract = StacksChainState::get_contract_at_block(
+            &mut conn,
+            &contract_id,
+            &StacksBlockId([1 as u8; 32]),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(contract.contract_context.contract_identifier, contract_id);
+
+        // the connection's own tip is restored afterwards
+        assert!(StacksChainState::get_contract(&mut conn, &contract_id)
+            .unwrap()
+            .is_some());
+
+        // not an ancestor of the tip
+        assert!(StacksChainState::get_contract_at_block(
+            &mut conn,
+            &contract_id,
+            &StacksBlockId([9 as u8; 32])
+        )
+        .is_err());
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_normalize_contract_deploy_error() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("twice").unwrap();
+        let contract = "(define-read-only (foo) 1)";
+        deploy_contract(&mut conn, &contract_id, contract);
+
+        // deploy it again: both the VM and the analysis DB refuse, each in its own way
+        let (initialize_err, save_err) = conn.as_transaction(|tx| {
+            let (ast, analysis) = tx.analyze_smart_contract(&contract_id, contract).unwrap();
+            let initialize_err = tx
+                .initialize_smart_contract(&contract_id, &ast, contract, |_, _| false)
+                .unwrap_err();
+            let save_err = tx.save_analysis(&contract_id, &analysis).unwrap_err();
+            (initialize_err, save_err)
+        });
+        assert!(format!("{:?}", &initialize_err).contains("Unchecked(ContractAlreadyExists"));
+        assert!(format!("{:?}", &save_err).contains("ContractAlreadyExists"));
+
+        let expected = Error::ContractAlreadyExists(contract_id.clone());
+        for err in vec![
+            Error::from(initialize_err),
+            Error::ClarityError(save_err.into()),
+            Error::DBError(db_error::ExistsError),
+        ] {
+            assert_eq!(
+                StacksChainState::normalize_contract_deploy_error(&contract_id, err),
+                expected
+            );
+        }
+
+        // other errors are unchanged
+        assert_eq!(
+            StacksChainState::normalize_contract_deploy_error(
+                &contract_id,
+                Error::DBError(db_error::NotFoundError)
+            ),
+            Error::DBError(db_error::NotFoundError)
+        );
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_contract_analysis() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("analyzed").unwrap();
+        deploy_contract(
+            &mut conn,
+            &contract_id,
+            "(define-public (add (a int) (b uint)) (ok (+ a (to-int b))))",
+        );
+
+        let analysis = StacksChainState::get_contract_analysis(&mut conn, &contract_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(analysis.contract_identifier, contract_id);
+        match analysis.get_public_function_type("add") {
+            Some(FunctionType::Fixed(FixedFunction { args, returns })) => {
+                assert_eq!(args.len(), 2);
+                assert_eq!(args[0].name.as_str(), "a");
+                assert_eq!(args[0].signature, TypeSignature::IntType);
+                assert_eq!(args[1].name.as_str(), "b");
+                assert_eq!(args[1].signature, TypeSignature::UIntType);
+                assert_eq!(
+                    *returns,
+                    TypeSignature::new_response(TypeSignature::IntType, TypeSignature::NoType)
+                        .unwrap()
+                );
+            }
+            x => panic!("Expected a fixed function type for 'add', got {:?}", &x),
+        }
+
+        assert!(StacksChainState::get_contract_analysis(
+            &mut conn,
+            &QualifiedContractIdentifier::local("missing").unwrap()
+        )
+        .unwrap()
+        .is_none());
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_public_function_signature() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("signatures").unwrap();
+        deploy_contract(
+            &mut conn,
+            &contract_id,
+            "(define-public (add (a int) (b uint)) (ok (+ a (to-int b))))
+             (define-read-only (get-one) 1)",
+        );
+
+        assert_eq!(
+            StacksChainState::get_public_function_signature(&mut conn, &contract_id, "add")
+                .unwrap(),
+            Some(FunctionSignature {
+                args: vec![TypeSignature::IntType, TypeSignature::UIntType],
+                returns: TypeSignature::new_response(TypeSignature::IntType, TypeSignature::NoType)
+                    .unwrap(),
+            })
+        );
+
+        // not a public function
+        assert!(StacksChainState::get_public_function_signature(
+            &mut conn,
+            &contract_id,
+            "get-one"
+        )
+        .unwrap()
+        .is_none());
+        assert!(StacksChainState::get_public_function_signature(
+            &mut conn,
+            &contract_id,
+            "missing"
+        )
+        .unwrap()
+        .is_none());
+
+        let missing_id = QualifiedContractIdentifier::local("missing").unwrap();
+        match StacksChainState::get_public_function_signature(&mut conn, &missing_id, "add") {
+            Err(Error::ClarityError(clarity_error::Analysis(CheckError {
+                err: CheckErrors::NoSuchContract(id),
+                ..
+            }))) => assert_eq!(id, missing_id.to_string()),
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_contract_size() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("sized").unwrap();
+        let contract = "(define-constant greeting \"hello\")
+             (define-read-only (get-greeting) greeting)";
+        deploy_contract(&mut conn, &contract_id, contract);
+
+        assert_eq!(
+            StacksChainState::get_contract_size(&mut conn, &contract_id).unwrap(),
+            Some(contract.len() as u64)
+        );
+        assert_eq!(
+            StacksChainState::get_contract_size(
+                &mut conn,
+                &QualifiedContractIdentifier::local("missing").unwrap()
+            )
+            .unwrap(),
+            None
+        );
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_list_data_vars() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let vars_id = QualifiedContractIdentifier::local("vars").unwrap();
+        deploy_contract(
+            &mut conn,
+            &vars_id,
+            "(define-data-var owner principal tx-sender)
+             (define-data-var count int 0)
+             (define-constant limit 10)
+             (define-map balances principal uint)",
+        );
+        let no_vars_id = QualifiedContractIdentifier::local("no-vars").unwrap();
+        deploy_contract(&mut conn, &no_vars_id, "(define-read-only (foo) 1)");
+
+        assert_eq!(
+            StacksChainState::list_data_vars(&mut conn, &vars_id).unwrap(),
+            vec!["count".to_string(), "owner".to_string()]
+        );
+        assert!(StacksChainState::list_data_vars(&mut conn, &no_vars_id)
+            .unwrap()
+            .is_empty());
+
+        match StacksChainState::list_data_vars(
+            &mut conn,
+            &QualifiedContractIdentifier::local("missing").unwrap(),
+        ) {
+            Err(Error::ClarityError(clarity_error::Analysis(CheckError {
+                err: CheckErrors::NoSuchContract(_),
+                ..
+            }))) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_map_entry() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("maps").unwrap();
+        deploy_contract(
+            &mut conn,
+            &contract_id,
+            "(define-map scores int uint)
+             (map-set scores 1 u100)",
+        );
+
+        assert_eq!(
+            StacksChainState::get_map_entry(&mut conn, &contract_id, "scores", &Value::Int(1))
+                .unwrap(),
+            Some(Value::UInt(100))
+        );
+        assert_eq!(
+            StacksChainState::get_map_entry(&mut conn, &contract_id, "scores", &Value::Int(2))
+                .unwrap(),
+            None
+        );
+        match StacksChainState::get_map_entry(&mut conn, &contract_id, "nope", &Value::Int(1)) {
+            Err(Error::ClarityError(clarity_error::Interpreter(clarity_vm_error::Unchecked(
+                CheckErrors::NoSuchMap(_),
+            )))) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_data_vars() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("vars").unwrap();
+        deploy_contract(
+            &mut conn,
+            &contract_id,
+            "(define-data-var count int 7)
+             (define-data-var enabled bool true)",
+        );
+
+        let values = StacksChainState::get_data_vars(
+            &mut conn,
+            &contract_id,
+            &["count", "missing", "enabled"],
+        )
+        .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                ("count".to_string(), Some(Value::Int(7))),
+                ("missing".to_string(), None),
+                ("enabled".to_string(), Some(Value::Bool(true))),
+            ]
+        );
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_get_contract_src() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let contract_id = QualifiedContractIdentifier::local("src").unwrap();
+        let contract = "(define-data-var count int 0)
+             ;; a comment that must survive
+             (define-read-only (get-count) (var-get count))";
+        deploy_contract(&mut conn, &contract_id, contract);
+
+        assert_eq!(
+            StacksChainState::get_contract_src(&mut conn, &contract_id).unwrap(),
+            Some(contract.to_string())
+        );
+        assert_eq!(
+            StacksChainState::get_contract_src(
+                &mut conn,
+                &QualifiedContractIdentifier::local("missing").unwrap()
+            )
+            .unwrap(),
+            None
+        );
+
+        conn.commit_block();
+    }
+
+    #[test]
+    fn test_contract_conforms_to_trait() {
+        let mut clarity_instance = new_test_instance();
+        let mut conn = begin_test_block(&mut clarity_instance);
+
+        let trait_contract_id = QualifiedContractIdentifier::local("nft-trait").unwrap();
+        let conforming_id = QualifiedContractIdentifier::local("conforming").unwrap();
+        let nonconforming_id = QualifiedContractIdentifier::local("nonconforming").unwrap();
+        let missing_id = QualifiedContractIdentifier::local("missing").unwrap();
+
+        deploy_contract(
+            &mut conn,
+            &trait_contract_id,
+            "(define-trait nft-trait ((get-owner (uint) (response (optional principal) uint))))",
+        );
+        deploy_contract(
+            &mut conn,
+            &conforming_id,
+            "(define-read-only (get-owner (id uint)) (ok (some tx-sender)))",
+        );
+        deploy_contract(
+            &mut conn,
+            &nonconforming_id,
+            "(define-read-only (get-owner (id int)) (ok (some tx-sender)))",
+        );
+
+        let trait_id = TraitIdentifier {
+            name: "nft-trait".into(),
+            contract_identifier: trait_contract_id.clone(),
+        };
+
+        assert!(
+            StacksChainState::contract_conforms_to_trait(&mut conn, &conforming_id, &trait_id)
+                .unwrap()
+        );
+        assert!(!StacksChainState::contract_conforms_to_trait(
+            &mut conn,
+            &nonconforming_id,
+            &trait_id
+        )
+        .unwrap());
+
+        // missing contract
+        match StacksChainState::contract_conforms_to_trait(&mut conn, &missing_id, &trait_id) {
+            Err(Error::ClarityError(clarity_error::Analysis(CheckError {
+                err: CheckErrors::NoSuchContract(id),
+                ..
+            }))) => assert_eq!(id, missing_id.to_string()),
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // passing a missing contract where a trait is expected names the whole contract id
+        let caller_id = QualifiedContractIdentifier::local("caller").unwrap();
+        deploy_contract(
+            &mut conn,
+            &caller_id,
+            "(use-trait nft-trait .nft-trait.nft-trait)
+             (define-public (call (nft <nft-trait>)) (ok true))",
+        );
+        let res = conn.with_analysis_db_readonly(|db| {
+            let function_type = db
+                .get_public_function_type(&caller_id, "call")
+                .unwrap()
+                .unwrap();
+            function_type.check_args_by_allowing_trait_cast(
+                db,
+                &[Value::Principal(PrincipalData::Contract(
+                    missing_id.clone(),
+                ))],
+            )
+        });
+        match res {
+            Err(CheckError {
+                err: CheckErrors::NoSuchContract(id),
+                ..
+            }) => assert_eq!(id, missing_id.to_string()),
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // missing trait
+        let missing_trait_id = TraitIdentifier {
+            name: "no-such-trait".into(),
+            contract_identifier: trait_contract_id.clone(),
+        };
+        match StacksChainState::contract_conforms_to_trait(
+            &mut conn,
+            &conforming_id,
+            &missing_trait_id,
+        ) {
+            Err(Error::ClarityError(clarity_error::Analysis(CheckError {
+                err: CheckErrors::TraitReferenceUnknown(_),
+                ..
+            }))) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        conn.commit_block();
+    }
 }
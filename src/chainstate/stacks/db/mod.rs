@@ -35,6 +35,8 @@ use std::io;
 use std::io::prelude::*;
 
 use std::ops::{Deref, DerefMut};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use core::*;
 
@@ -693,6 +695,71 @@ impl ChainStateBootData {
     }
 }
 
+/// Run `f`, and log a warning if it took longer than `limit` to complete.
+/// The result of `f` is returned no matter how long it took.
+pub fn with_timeout<F, R>(op: &'static str, limit: Duration, f: F) -> Result<R, Error>
+where
+    F: FnOnce() -> Result<R, Error>,
+{
+    let start = Instant::now();
+    let res = f();
+    let elapsed = start.elapsed();
+    if elapsed > limit {
+        warn!(
+            "Chainstate operation '{}' took {:?} (limit is {:?})",
+            op, elapsed, limit
+        );
+    }
+    res
+}
+
+/// Run `f` with a hard deadline.  If `f` succeeds but took longer than `limit`, then return
+/// `Error::TimedOutOperation` instead of its result.  Note that `f` is not interrupted.
+pub fn with_deadline<F, R>(op: &'static str, limit: Duration, f: F) -> Result<R, Error>
+where
+    F: FnOnce() -> Result<R, Error>,
+{
+    let start = Instant::now();
+    let res = f()?;
+    let elapsed = start.elapsed();
+    if elapsed > limit {
+        warn!(
+            "Chainstate operation '{}' took {:?} (deadline is {:?})",
+            op, elapsed, limit
+        );
+        return Err(Error::TimedOutOperation { op, elapsed, limit });
+    }
+    Ok(res)
+}
+
+/// Run `f` up to `max_attempts` times, sleeping `backoff` between attempts, for as long as it
+/// fails because the database is busy.  Any other result is returned immediately.  If every
+/// attempt finds the database busy, the last busy error is returned.
+pub fn with_db_retry<F, R>(max_attempts: u32, backoff: Duration, mut f: F) -> Result<R, Error>
+where
+    F: FnMut() -> Result<R, Error>,
+{
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(e) => {
+                if !e.is_db_busy() || attempt >= max_attempts {
+                    return Err(e);
+                }
+                debug!(
+                    "Database is busy (attempt {} of {}); retrying in {:?}",
+                    attempt, max_attempts, backoff
+                );
+                attempt += 1;
+                thread::sleep(backoff);
+            }
+            res => {
+                return res;
+            }
+        }
+    }
+}
+
 impl StacksChainState {
     fn instantiate_db(
         mainnet: bool,
@@ -1938,6 +2005,7 @@ pub mod test {
     use std::fs;
 
     use stx_genesis::GenesisData;
+    use util::sleep_ms;
     use vm::database::NULL_BURN_STATE_DB;
 
     pub fn instantiate_chainstate(
@@ -2023,6 +2091,85 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn chainstate_op_timeouts() {
+        let slow_op = || {
+            sleep_ms(10);
+            Ok(123)
+        };
+
+        // soft limit: the result is still returned
+        assert_eq!(
+            with_timeout("slow-op", Duration::from_millis(1), slow_op).unwrap(),
+            123
+        );
+        assert_eq!(
+            with_timeout("fast-op", Duration::from_secs(60), || Ok(456)).unwrap(),
+            456
+        );
+
+        // hard limit: the operation is reported as timed out
+        match with_deadline("slow-op", Duration::from_millis(1), slow_op) {
+            Err(Error::TimedOutOperation { op, elapsed, limit }) => {
+                assert_eq!(op, "slow-op");
+                assert_eq!(limit, Duration::from_millis(1));
+                assert!(elapsed >= Duration::from_millis(10));
+            }
+            x => panic!("Expected TimedOutOperation, got {:?}", &x),
+        }
+        assert_eq!(
+            with_deadline("fast-op", Duration::from_secs(60), || Ok(456)).unwrap(),
+            456
+        );
+
+        // errors from the operation itself pass through
+        match with_deadline::<_, ()>("failed-op", Duration::from_secs(60), || {
+            Err(Error::NoSuchBlockError)
+        }) {
+            Err(Error::NoSuchBlockError) => {}
+            x => panic!("Expected NoSuchBlockError, got {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn chainstate_db_retry() {
+        // succeeds on the second try
+        let mut attempts = 0;
+        let res = with_db_retry(3, Duration::from_millis(1), || {
+            attempts += 1;
+            if attempts < 2 {
+                Err(Error::DBError(db_error::Busy))
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(res.unwrap(), 2);
+
+        // always busy
+        let mut attempts = 0;
+        let res = with_db_retry::<_, ()>(3, Duration::from_millis(1), || {
+            attempts += 1;
+            Err(Error::DBError(db_error::Busy))
+        });
+        match res {
+            Err(Error::DBError(db_error::Busy)) => {}
+            x => panic!("Expected DBError(Busy), got {:?}", &x),
+        }
+        assert_eq!(attempts, 3);
+
+        // non-transient errors are not retried
+        let mut attempts = 0;
+        let res = with_db_retry::<_, ()>(3, Duration::from_millis(1), || {
+            attempts += 1;
+            Err(Error::DBError(db_error::Corruption))
+        });
+        match res {
+            Err(Error::DBError(db_error::Corruption)) => {}
+            x => panic!("Expected DBError(Corruption), got {:?}", &x),
+        }
+        assert_eq!(attempts, 1);
+    }
+
     #[test]
     fn test_chainstate_sampled_genesis_consistency() {
         // Test root hash for the test chainstate data set
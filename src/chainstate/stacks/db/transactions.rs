@@ -252,7 +252,7 @@ impl std::fmt::Display for TransactionNonceMismatch {
 
 impl<T> From<(TransactionNonceMismatch, T)> for Error {
     fn from(e: (TransactionNonceMismatch, T)) -> Error {
-        Error::InvalidStacksTransaction(e.0.to_string(), e.0.quiet)
+        Error::BadNonce(e.0)
     }
 }
 
@@ -290,7 +290,10 @@ fn handle_clarity_runtime_error(error: clarity_error) -> ClarityRuntimeTxError {
         clarity_error::AbortedByCallback(val, assets, events) => {
             ClarityRuntimeTxError::AbortedByCallback(val, assets, events)
         }
-        clarity_error::CostError(cost, budget) => ClarityRuntimeTxError::CostError(cost, budget),
+        clarity_error::CostError(cost, budget, _) => ClarityRuntimeTxError::CostError(cost, budget),
+        clarity_error::MemoryBalanceExceeded(..) => {
+            ClarityRuntimeTxError::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
+        }
         unhandled_error => ClarityRuntimeTxError::Rejectable(unhandled_error),
     }
 }
@@ -419,18 +422,18 @@ impl StacksChainState {
         match tx.version {
             TransactionVersion::Mainnet => {
                 if !config.mainnet {
-                    let msg = format!("Invalid tx {}: on testnet; got mainnet", tx.txid());
-                    warn!("{}", &msg);
+                    let err = invalid_tx!(tx.txid(), "on testnet; got mainnet");
+                    warn!("{}", &err);
 
-                    return Err(Error::InvalidStacksTransaction(msg, false));
+                    return Err(err);
                 }
             }
             TransactionVersion::Testnet => {
                 if config.mainnet {
-                    let msg = format!("Invalid tx {}: on mainnet; got testnet", tx.txid());
-                    warn!("{}", &msg);
+                    let err = invalid_tx!(tx.txid(), "on mainnet; got testnet");
+                    warn!("{}", &err);
 
-                    return Err(Error::InvalidStacksTransaction(msg, false));
+                    return Err(err);
                 }
             }
         }
@@ -981,7 +984,7 @@ impl StacksChainState {
                     Ok(x) => x,
                     Err(e) => {
                         match e {
-                            clarity_error::CostError(ref cost_after, ref budget) => {
+                            clarity_error::CostError(ref cost_after, ref budget, _) => {
                                 warn!("Block compute budget exceeded on {}: cost before={}, after={}, budget={}", tx.txid(), &cost_before, cost_after, budget);
                                 return Err(Error::CostOverflowError(
                                     cost_before,
@@ -989,6 +992,19 @@ impl StacksChainState {
                                     budget.clone(),
                                 ));
                             }
+                            clarity_error::MemoryBalanceExceeded(ref used, ref limit) => {
+                                warn!(
+                                    "Memory budget exceeded on {}: used={}, limit={}",
+                                    tx.txid(),
+                                    used,
+                                    limit
+                                );
+                                return Err(Error::CostOverflowError(
+                                    cost_before,
+                                    ExecutionCost::max_value(),
+                                    ExecutionCost::max_value(),
+                                ));
+                            }
                             _ => {
                                 // this analysis isn't free -- convert to runtime error
                                 let mut analysis_cost = clarity_tx.cost_so_far();
@@ -1480,6 +1496,9 @@ pub mod test {
                 Err(Error::InvalidStacksTransaction(msg, false)) => {
                     assert!(msg.contains(&err_frag), err_frag);
                 }
+                Err(Error::BadNonce(ref e)) if !e.quiet => {
+                    assert!(e.to_string().contains(&err_frag), err_frag);
+                }
                 _ => {
                     eprintln!("bad error: {:?}", &res);
                     eprintln!("Expected '{}'", &err_frag);
@@ -2170,6 +2189,91 @@ pub mod test {
         conn.commit_block();
     }
 
+    #[test]
+    fn process_smart_contract_contract_call_memory_exceeded() {
+        // each binding holds a 1MB buffer, so calling `explode` exceeds the memory limit
+        let mut contract = "(define-constant buff-0 0x00)".to_string();
+        for i in 0..20 {
+            contract.push_str(&format!(
+                "\n(define-constant buff-{} (concat buff-{} buff-{}))",
+                i + 1,
+                i,
+                i
+            ));
+        }
+        contract.push_str("\n(define-public (explode) (let (");
+        for i in 0..100 {
+            contract.push_str(&format!("(var-{} buff-20) ", i));
+        }
+        contract.push_str(") (ok 1)))");
+
+        let mut chainstate = instantiate_chainstate(
+            false,
+            0x80000000,
+            "process-smart-contract-call-memory-exceeded",
+        );
+
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+
+        let mut tx_contract = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth.clone(),
+            TransactionPayload::new_smart_contract(&"hello-world".to_string(), &contract).unwrap(),
+        );
+
+        tx_contract.chain_id = 0x80000000;
+        tx_contract.set_tx_fee(0);
+
+        let mut signer = StacksTransactionSigner::new(&tx_contract);
+        signer.sign_origin(&privk).unwrap();
+
+        let signed_tx = signer.get_tx().unwrap();
+
+        let mut conn = chainstate.block_begin(
+            &NULL_BURN_STATE_DB,
+            &FIRST_BURNCHAIN_CONSENSUS_HASH,
+            &FIRST_STACKS_BLOCK_HASH,
+            &ConsensusHash([1u8; 20]),
+            &BlockHeaderHash([1u8; 32]),
+        );
+
+        let (_fee, _) =
+            StacksChainState::process_transaction(&mut conn, &signed_tx, false).unwrap();
+
+        let privk_2 = StacksPrivateKey::from_hex(
+            "d2c340ebcc0794b6fabdd8ac8b1c983e363b05dc8adcdf7e30db205a3fa54c1601",
+        )
+        .unwrap();
+        let auth_2 = TransactionAuth::from_p2pkh(&privk_2).unwrap();
+
+        let mut tx_contract_call = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth_2.clone(),
+            TransactionPayload::new_contract_call(addr.clone(), "hello-world", "explode", vec![])
+                .unwrap(),
+        );
+
+        tx_contract_call.chain_id = 0x80000000;
+        tx_contract_call.set_tx_fee(0);
+
+        let mut signer_2 = StacksTransactionSigner::new(&tx_contract_call);
+        signer_2.sign_origin(&privk_2).unwrap();
+
+        let signed_tx_2 = signer_2.get_tx().unwrap();
+
+        // the miner relies on this being a cost overflow, so it can reset the block's cost
+        match StacksChainState::process_transaction(&mut conn, &signed_tx_2, false) {
+            Err(Error::CostOverflowError(..)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+        conn.commit_block();
+    }
+
     #[test]
     fn process_smart_contract_user_aborts_2257() {
         let contract = "(asserts! false (err 1))";
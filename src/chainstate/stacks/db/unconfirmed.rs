@@ -486,7 +486,7 @@ mod test {
     use chainstate::burn::db::sortdb::*;
     use chainstate::burn::db::*;
 
-    use chainstate::stacks::miner::test::make_coinbase;
+    use chainstate::stacks::miner::test::{make_coinbase, make_user_contract_publish};
     use core::mempool::*;
 
     #[test]
@@ -956,4 +956,165 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_unconfirmed_get_contract_latest() {
+        let privk = StacksPrivateKey::new();
+        let addr = StacksAddress::from_public_keys(
+            C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+            &AddressHashMode::SerializeP2PKH,
+            1,
+            &vec![StacksPublicKey::from_private(&privk)],
+        )
+        .unwrap();
+
+        let initial_balance = 1000000000;
+        let mut peer_config =
+            TestPeerConfig::new("test_unconfirmed_get_contract_latest", 7004, 7005);
+        peer_config.initial_balances = vec![(addr.to_account_principal(), initial_balance)];
+
+        let mut peer = TestPeer::new(peer_config);
+
+        let confirmed_contract_id =
+            QualifiedContractIdentifier::new(addr.clone().into(), "confirmed".into());
+        let unconfirmed_contract_id =
+            QualifiedContractIdentifier::new(addr.clone().into(), "unconfirmed".into());
+
+        let microblock_privkey = StacksPrivateKey::new();
+        let microblock_pubkeyhash =
+            Hash160::from_node_public_key(&StacksPublicKey::from_private(&microblock_privkey));
+
+        let tip = SortitionDB::get_canonical_burn_chain_tip(&peer.sortdb.as_ref().unwrap().conn())
+            .unwrap();
+
+        // anchored block deploys the confirmed contract
+        let (burn_ops, stacks_block, _) = peer.make_tenure(
+            |ref mut miner, ref mut sortdb, ref mut chainstate, vrf_proof, ref parent_opt, _| {
+                assert!(parent_opt.is_none());
+                let parent_tip =
+                    StacksChainState::get_genesis_header_info(chainstate.db()).unwrap();
+
+                let block_builder = StacksBlockBuilder::make_regtest_block_builder(
+                    &parent_tip,
+                    vrf_proof,
+                    tip.total_burn,
+                    microblock_pubkeyhash,
+                )
+                .unwrap();
+
+                let coinbase_tx = make_coinbase(miner, 0);
+                let contract_tx =
+                    make_user_contract_publish(&privk, 0, 0, "confirmed", "(define-constant a 1)");
+                let (anchored_block, _, _) = StacksBlockBuilder::make_anchored_block_from_txs(
+                    block_builder,
+                    chainstate,
+                    &sortdb.index_conn(),
+                    vec![coinbase_tx, contract_tx],
+                )
+                .unwrap();
+                (anchored_block, vec![])
+            },
+        );
+
+        let (_, _, consensus_hash) = peer.next_burnchain_block(burn_ops.clone());
+        peer.process_stacks_epoch_at_tip(&stacks_block, &vec![]);
+
+        let canonical_tip =
+            StacksBlockHeader::make_index_block_hash(&consensus_hash, &stacks_block.block_hash());
+
+        // microblock deploys the unconfirmed contract
+        let microblock = {
+            let sortdb = peer.sortdb.take().unwrap();
+            let sort_iconn = sortdb.index_conn();
+
+            peer.chainstate()
+                .reload_unconfirmed_state(&sort_iconn, canonical_tip.clone())
+                .unwrap();
+
+            let microblock = {
+                let mut microblock_builder = StacksMicroblockBuilder::new(
+                    stacks_block.block_hash(),
+                    consensus_hash.clone(),
+                    peer.chainstate(),
+                    &sort_iconn,
+                )
+                .unwrap();
+
+                let contract_tx = make_user_contract_publish(
+                    &privk,
+                    1,
+                    0,
+                    "unconfirmed",
+                    "(define-constant b 2)",
+                );
+                let contract_tx_len = contract_tx.serialize_to_vec().len() as u64;
+
+                microblock_builder
+                    .mine_next_microblock_from_txs(
+                        vec![(contract_tx, contract_tx_len)],
+                        &microblock_privkey,
+                    )
+                    .unwrap()
+            };
+            peer.sortdb = Some(sortdb);
+            microblock
+        };
+
+        peer.chainstate()
+            .preprocess_streamed_microblock(
+                &consensus_hash,
+                &stacks_block.block_hash(),
+                &microblock,
+            )
+            .unwrap();
+
+        let sortdb = peer.sortdb.take().unwrap();
+        peer.chainstate()
+            .reload_unconfirmed_state(&sortdb.index_conn(), canonical_tip.clone())
+            .unwrap();
+
+        let mut get_latest = |contract_id: &QualifiedContractIdentifier, prefer_unconfirmed| {
+            peer.chainstate()
+                .get_contract_latest(
+                    &sortdb.index_conn(),
+                    &canonical_tip,
+                    contract_id,
+                    prefer_unconfirmed,
+                )
+                .unwrap()
+        };
+
+        // only in the unconfirmed state
+        assert!(get_latest(&unconfirmed_contract_id, true).is_some());
+        assert!(get_latest(&unconfirmed_contract_id, false).is_none());
+
+        // in both the confirmed and unconfirmed state
+        assert!(get_latest(&confirmed_contract_id, true).is_some());
+        assert!(get_latest(&confirmed_contract_id, false).is_some());
+
+        // only in the confirmed state -- i.e. without an unconfirmed state to consult
+        peer.chainstate().unconfirmed_state = None;
+        assert!(peer
+            .chainstate()
+            .get_contract_latest(
+                &sortdb.index_conn(),
+                &canonical_tip,
+                &confirmed_contract_id,
+                true
+            )
+            .unwrap()
+            .is_some());
+        assert!(peer
+            .chainstate()
+            .get_contract_latest(
+                &sortdb.index_conn(),
+                &canonical_tip,
+                &unconfirmed_contract_id,
+                true
+            )
+            .unwrap()
+            .is_none());
+
+        peer.sortdb = Some(sortdb);
+    }
 }
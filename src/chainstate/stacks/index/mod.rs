@@ -311,6 +311,17 @@ impl From<db_error> for Error {
     }
 }
 
+impl Error {
+    /// Does this error mean that the requested data does not exist, either directly or because
+    /// a back-pointer to it could not be followed?
+    pub fn is_not_found(&self) -> bool {
+        match *self {
+            Error::NotFoundError | Error::BackptrNotFoundError => true,
+            _ => false,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -342,9 +353,12 @@ impl fmt::Display for Error {
                 f,
                 "Failed to restore previous open block during block header check"
             ),
-            Error::NonMatchingForks(_, _) => {
-                write!(f, "The supplied blocks are not in the same fork")
-            }
+            Error::NonMatchingForks(ref bh1, ref bh2) => write!(
+                f,
+                "The supplied blocks are not in the same fork ({} and {})",
+                to_hex(bh1),
+                to_hex(bh2)
+            ),
             Error::RequestedIdentifierForExtensionTrie => {
                 write!(f, "BUG: MARF requested the identifier for a RAM trie")
             }
@@ -353,7 +367,7 @@ impl fmt::Display for Error {
 }
 
 impl error::Error for Error {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::IOError(ref e) => Some(e),
             Error::SQLError(ref e) => Some(e),
@@ -711,4 +725,20 @@ mod test {
     ) -> (Vec<TrieNodeType>, Vec<TriePtr>, Vec<TrieHash>) {
         make_node_path(s, TrieNodeID::Node4 as u8, path_segments, leaf_data)
     }
+
+    #[test]
+    fn is_not_found() {
+        assert!(Error::NotFoundError.is_not_found());
+        assert!(Error::BackptrNotFoundError.is_not_found());
+        assert!(!Error::CorruptionError("bad".to_string()).is_not_found());
+        assert!(!Error::ExistsError.is_not_found());
+    }
+
+    #[test]
+    fn non_matching_forks_display() {
+        let err = Error::NonMatchingForks([0x01; 32], [0xab; 32]);
+        let msg = err.to_string();
+        assert!(msg.contains(&to_hex(&[0x01; 32])), "{}", &msg);
+        assert!(msg.contains(&to_hex(&[0xab; 32])), "{}", &msg);
+    }
 }
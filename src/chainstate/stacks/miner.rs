@@ -1172,13 +1172,9 @@ impl StacksBlockBuilder {
                     // Make the block from the transactions we did manage to get
                     debug!("Block budget exceeded on tx {}", &tx.txid());
                 }
-                Err(Error::InvalidStacksTransaction(_emsg, true)) => {
+                Err(ref e) if e.is_quiet_rejection() == Some(true) => {
                     // if we have an invalid transaction that was quietly ignored, don't warn here either
-                    test_debug!(
-                        "Failed to apply tx {}: InvalidStacksTransaction '{:?}'",
-                        &tx.txid(),
-                        &_emsg
-                    );
+                    test_debug!("Failed to apply tx {}: {:?}", &tx.txid(), e);
                     continue;
                 }
                 Err(e) => {
@@ -1302,9 +1298,9 @@ impl StacksBlockBuilder {
     ) -> Result<(StacksBlock, ExecutionCost, u64), Error> {
         if let TransactionPayload::Coinbase(..) = coinbase_tx.payload {
         } else {
-            return Err(Error::MemPoolError(
+            return Err(Error::MemPoolError(Box::new(MemPoolRejection::Other(
                 "Not a coinbase transaction".to_string(),
-            ));
+            ))));
         }
 
         let (tip_consensus_hash, tip_block_hash, tip_height) = (
@@ -1399,7 +1395,7 @@ impl StacksBlockBuilder {
                                 block_limit_hit = BlockLimitFunction::LIMIT_REACHED;
                             }
                         }
-                        Err(Error::InvalidStacksTransaction(_, true)) => {
+                        Err(ref e) if e.is_quiet_rejection() == Some(true) => {
                             // if we have an invalid transaction that was quietly ignored, don't warn here either
                             continue;
                         }
@@ -33,6 +33,7 @@ use std::io::prelude::*;
 use std::io::{Read, Write};
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::time::Duration;
 
 use sha2::{Digest, Sha512Trunc256};
 use util::db::DBConn;
@@ -57,6 +58,7 @@ use chainstate::stacks::index::Error as marf_error;
 use chainstate::stacks::index::{TrieHash, TRIEHASH_ENCODED_SIZE};
 
 use chainstate::stacks::db::blocks::MemPoolRejection;
+use chainstate::stacks::db::transactions::TransactionNonceMismatch;
 use net::codec::{read_next, write_next};
 use net::Error as net_error;
 use net::{StacksMessageCodec, MAX_MESSAGE_LEN};
@@ -64,7 +66,9 @@ use rusqlite::Error as RusqliteError;
 
 use vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData, Value};
 
+use vm::analysis::CheckError;
 use vm::errors::Error as clarity_interpreter_error;
+use vm::errors::InterpreterError as clarity_interpreter_failure;
 
 use vm::clarity::Error as clarity_error;
 use vm::costs::CostErrors;
@@ -89,6 +93,9 @@ pub const STACKS_MICROBLOCK_VERSION: u8 = 0;
 
 pub const MAX_BLOCK_LEN: u32 = 2 * 1024 * 1024;
 pub const MAX_TRANSACTION_LEN: u32 = MAX_BLOCK_LEN;
+/// The mempool won't analyze a contract whose source is bigger than this.  It leaves room in a
+/// transaction for the rest of the payload, so a decodable transaction can still exceed it.
+pub const MAX_CONTRACT_SRC_LEN: u32 = MAX_TRANSACTION_LEN / 2;
 
 pub struct StacksBlockId(pub [u8; 32]);
 impl_array_newtype!(StacksBlockId, u8, 32);
@@ -148,11 +155,85 @@ impl AddressHashMode {
     }
 }
 
+/// Why STX could not be locked for PoX, or PoX state could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoxError {
+    /// The account already has this many uSTX locked, until this burnchain block height
+    AlreadyLocked {
+        amount_locked: u128,
+        unlock_height: u64,
+    },
+    /// Locking needs `required` uSTX, but the account only has `available` uSTX to spend
+    InsufficientBalance { required: u128, available: u128 },
+    /// The requested burnchain block height falls before the first reward cycle
+    NoRewardCycle { burn_height: u64 },
+}
+
+impl PoxError {
+    fn name(&self) -> &'static str {
+        match self {
+            PoxError::AlreadyLocked { .. } => "PoxAlreadyLocked",
+            PoxError::InsufficientBalance { .. } => "PoxInsufficientBalance",
+            PoxError::NoRewardCycle { .. } => "PoxNoRewardCycle",
+        }
+    }
+
+    fn error_code(&self) -> u32 {
+        match self {
+            PoxError::AlreadyLocked { .. } => 22,
+            PoxError::InsufficientBalance { .. } => 23,
+            PoxError::NoRewardCycle { .. } => 24,
+        }
+    }
+
+    /// uSTX amounts can exceed what a JSON number can hold, so they are reported as strings
+    pub fn into_json(&self) -> serde_json::Value {
+        match self {
+            PoxError::AlreadyLocked {
+                amount_locked,
+                unlock_height,
+            } => json!({
+                "amount_locked": amount_locked.to_string(),
+                "unlock_height": unlock_height,
+            }),
+            PoxError::InsufficientBalance {
+                required,
+                available,
+            } => json!({
+                "required": required.to_string(),
+                "available": available.to_string(),
+            }),
+            PoxError::NoRewardCycle { burn_height } => json!({
+                "burn_height": burn_height,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for PoxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoxError::AlreadyLocked { .. } => write!(f, "Account has already locked STX for PoX"),
+            PoxError::InsufficientBalance { .. } => write!(f, "Not enough STX to lock"),
+            PoxError::NoRewardCycle { .. } => write!(f, "No such reward cycle"),
+        }
+    }
+}
+
+impl error::Error for PoxError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     InvalidFee,
     InvalidStacksBlock(String),
     InvalidStacksMicroblock(String, BlockHeaderHash),
+    /// The transaction is invalid.  The flag is true if the transaction should be quietly
+    /// dropped (i.e. its rejection is routine and not worth a warning), and false if it should
+    /// be loudly rejected.
     InvalidStacksTransaction(String, bool),
     PostConditionFailed(String),
     NoSuchBlockError,
@@ -170,10 +251,23 @@ pub enum Error {
     MARFError(marf_error),
     ReadError(io::Error),
     WriteError(io::Error),
-    MemPoolError(String),
-    PoxAlreadyLocked,
-    PoxInsufficientBalance,
-    PoxNoRewardCycle,
+    /// Boxed, since a rejection can itself carry a chainstate error
+    MemPoolError(Box<MemPoolRejection>),
+    PoxError(PoxError),
+    TimedOutOperation {
+        op: &'static str,
+        elapsed: Duration,
+        limit: Duration,
+    },
+    /// A smart contract with this identifier has already been deployed
+    ContractAlreadyExists(QualifiedContractIdentifier),
+    /// A smart contract's source code is bigger than the maximum allowed size
+    ContractTooLarge {
+        size: u64,
+        max_size: u64,
+    },
+    /// A transaction's origin or sponsor nonce does not match its account's nonce
+    BadNonce(TransactionNonceMismatch),
 }
 
 impl From<marf_error> for Error {
@@ -225,17 +319,33 @@ impl fmt::Display for Error {
             Error::MARFError(ref e) => fmt::Display::fmt(e, f),
             Error::ReadError(ref e) => fmt::Display::fmt(e, f),
             Error::WriteError(ref e) => fmt::Display::fmt(e, f),
-            Error::MemPoolError(ref s) => fmt::Display::fmt(s, f),
+            Error::MemPoolError(ref r) => fmt::Display::fmt(r, f),
             Error::NoTransactionsToMine => write!(f, "No transactions to mine"),
-            Error::PoxAlreadyLocked => write!(f, "Account has already locked STX for PoX"),
-            Error::PoxInsufficientBalance => write!(f, "Not enough STX to lock"),
-            Error::PoxNoRewardCycle => write!(f, "No such reward cycle"),
+            Error::PoxError(ref e) => fmt::Display::fmt(e, f),
+            Error::TimedOutOperation {
+                ref op,
+                ref elapsed,
+                ref limit,
+            } => write!(
+                f,
+                "Operation '{}' took {:?}, exceeding its limit of {:?}",
+                op, elapsed, limit
+            ),
+            Error::ContractAlreadyExists(ref id) => {
+                write!(f, "Contract '{}' already exists", id)
+            }
+            Error::ContractTooLarge { size, max_size } => write!(
+                f,
+                "Contract source is {} bytes, exceeding the maximum of {} bytes",
+                size, max_size
+            ),
+            Error::BadNonce(ref e) => fmt::Display::fmt(e, f),
         }
     }
 }
 
 impl error::Error for Error {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::InvalidFee => None,
             Error::InvalidStacksBlock(ref _s) => None,
@@ -256,11 +366,13 @@ impl error::Error for Error {
             Error::MARFError(ref e) => Some(e),
             Error::ReadError(ref e) => Some(e),
             Error::WriteError(ref e) => Some(e),
-            Error::MemPoolError(ref _s) => None,
+            Error::MemPoolError(ref _r) => None,
             Error::NoTransactionsToMine => None,
-            Error::PoxAlreadyLocked => None,
-            Error::PoxInsufficientBalance => None,
-            Error::PoxNoRewardCycle => None,
+            Error::PoxError(ref e) => Some(e),
+            Error::TimedOutOperation { .. } => None,
+            Error::ContractAlreadyExists(..) => None,
+            Error::ContractTooLarge { .. } => None,
+            Error::BadNonce(..) => None,
         }
     }
 }
@@ -287,19 +399,116 @@ impl Error {
             Error::MARFError(ref _e) => "MARFError",
             Error::ReadError(ref _e) => "ReadError",
             Error::WriteError(ref _e) => "WriteError",
-            Error::MemPoolError(ref _s) => "MemPoolError",
+            Error::MemPoolError(ref _r) => "MemPoolError",
             Error::NoTransactionsToMine => "NoTransactionsToMine",
-            Error::PoxAlreadyLocked => "PoxAlreadyLocked",
-            Error::PoxInsufficientBalance => "PoxInsufficientBalance",
-            Error::PoxNoRewardCycle => "PoxNoRewardCycle",
+            Error::PoxError(ref e) => e.name(),
+            Error::TimedOutOperation { .. } => "TimedOutOperation",
+            Error::ContractAlreadyExists(..) => "ContractAlreadyExists",
+            Error::ContractTooLarge { .. } => "ContractTooLarge",
+            Error::BadNonce(..) => "BadNonce",
+        }
+    }
+
+    /// Stable, machine-readable code for this error, reported to API clients as `"code"`.
+    /// These values are part of the public API: never renumber or reuse a code, and give new
+    /// variants the next unused value.
+    ///
+    /// | code | variant |
+    /// |------|---------|
+    /// | 1 | InvalidFee |
+    /// | 2 | InvalidStacksBlock |
+    /// | 3 | InvalidStacksMicroblock |
+    /// | 4 | InvalidStacksTransaction |
+    /// | 5 | PostConditionFailed |
+    /// | 6 | NoSuchBlockError |
+    /// | 7 | InvalidChainstateDB |
+    /// | 8 | BlockTooBigError |
+    /// | 9 | TransactionTooBigError |
+    /// | 10 | BlockCostExceeded |
+    /// | 11 | NoTransactionsToMine |
+    /// | 12 | MicroblockStreamTooLongError |
+    /// | 13 | IncompatibleSpendingConditionError |
+    /// | 14 | CostOverflowError |
+    /// | 15 | ClarityError |
+    /// | 16 | DBError |
+    /// | 17 | NetError |
+    /// | 18 | MARFError |
+    /// | 19 | ReadError |
+    /// | 20 | WriteError |
+    /// | 21 | MemPoolError |
+    /// | 22 | PoxError (AlreadyLocked) |
+    /// | 23 | PoxError (InsufficientBalance) |
+    /// | 24 | PoxError (NoRewardCycle) |
+    /// | 25 | TimedOutOperation |
+    /// | 26 | ContractAlreadyExists |
+    /// | 27 | ContractTooLarge |
+    /// | 28 | BadNonce |
+    pub fn error_code(&self) -> u32 {
+        match self {
+            Error::InvalidFee => 1,
+            Error::InvalidStacksBlock(..) => 2,
+            Error::InvalidStacksMicroblock(..) => 3,
+            Error::InvalidStacksTransaction(..) => 4,
+            Error::PostConditionFailed(..) => 5,
+            Error::NoSuchBlockError => 6,
+            Error::InvalidChainstateDB => 7,
+            Error::BlockTooBigError => 8,
+            Error::TransactionTooBigError => 9,
+            Error::BlockCostExceeded => 10,
+            Error::NoTransactionsToMine => 11,
+            Error::MicroblockStreamTooLongError => 12,
+            Error::IncompatibleSpendingConditionError => 13,
+            Error::CostOverflowError(..) => 14,
+            Error::ClarityError(..) => 15,
+            Error::DBError(..) => 16,
+            Error::NetError(..) => 17,
+            Error::MARFError(..) => 18,
+            Error::ReadError(..) => 19,
+            Error::WriteError(..) => 20,
+            Error::MemPoolError(..) => 21,
+            Error::PoxError(ref e) => e.error_code(),
+            Error::TimedOutOperation { .. } => 25,
+            Error::ContractAlreadyExists(..) => 26,
+            Error::ContractTooLarge { .. } => 27,
+            Error::BadNonce(..) => 28,
         }
     }
 
     pub fn into_json(&self) -> serde_json::Value {
         let reason_code = self.name();
-        let reason_data = format!("{:?}", &self);
+        let reason_data = match self {
+            // report costs structurally, so they can be consumed directly
+            Error::CostOverflowError(ref before, ref after, ref budget) => json!({
+                "cost_before": before,
+                "cost_after": after,
+                "budget": budget,
+            }),
+            Error::ContractTooLarge { size, max_size } => json!({
+                "size": size,
+                "max_size": max_size,
+            }),
+            Error::BadNonce(ref e) => json!({
+                "expected": e.expected,
+                "actual": e.actual,
+                "principal": e.principal.to_string(),
+                "is_origin": e.is_origin,
+            }),
+            Error::PoxError(ref e) => e.into_json(),
+            // keep Clarity's own structure, so analysis diagnostics reach the client intact
+            Error::ClarityError(ref e) => e.into_json(),
+            // report the mempool's own reason, so clients can tell rejections apart
+            Error::MemPoolError(ref rejection) => {
+                let (reason, reason_data) = rejection.reason();
+                json!({
+                    "reason": reason,
+                    "reason_data": reason_data,
+                })
+            }
+            _ => json!(format!("{:?}", &self)),
+        };
         let result = json!({
             "error": "chainstate error",
+            "code": self.error_code(),
             "reason": reason_code,
             "reason_data": reason_data
         });
@@ -325,7 +534,41 @@ impl From<clarity_interpreter_error> for Error {
     }
 }
 
+impl From<clarity_interpreter_failure> for Error {
+    fn from(e: clarity_interpreter_failure) -> Error {
+        Error::ClarityError(clarity_error::Interpreter(
+            clarity_interpreter_error::Interpreter(e),
+        ))
+    }
+}
+
+impl From<CheckError> for Error {
+    fn from(e: CheckError) -> Error {
+        Error::ClarityError(clarity_error::Analysis(e))
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Error {
+    /// (make chainstate errors comparable for testing purposes)
+    fn eq(&self, other: &Self) -> bool {
+        let s1 = format!("{:?}", self);
+        let s2 = format!("{:?}", other);
+        s1 == s2
+    }
+}
+
 impl Error {
+    /// Make a `CostOverflowError` from the cost before the operation, the cost after it, and the
+    /// budget it exceeded.
+    pub fn cost_overflow(
+        before: ExecutionCost,
+        used: ExecutionCost,
+        budget: ExecutionCost,
+    ) -> Error {
+        Error::CostOverflowError(before, used, budget)
+    }
+
     pub fn from_cost_error(
         err: CostErrors,
         cost_before: ExecutionCost,
@@ -333,17 +576,128 @@ impl Error {
     ) -> Error {
         match err {
             CostErrors::CostBalanceExceeded(used, budget) => {
-                Error::CostOverflowError(cost_before, used, budget)
+                Error::cost_overflow(cost_before, used, budget)
             }
             _ => {
                 let cur_cost = context.cost_track.get_total();
                 let budget = context.cost_track.get_limit();
-                Error::CostOverflowError(cost_before, cur_cost, budget)
+                Error::cost_overflow(cost_before, cur_cost, budget)
             }
         }
     }
 }
 
+impl Error {
+    /// If this is a `CostOverflowError`, by how much did the cost after the overflow exceed the
+    /// budget?  Each dimension is computed separately, and is 0 if it stayed within budget.
+    /// Returns None for all other errors.
+    pub fn cost_overrun(&self) -> Option<ExecutionCost> {
+        match *self {
+            Error::CostOverflowError(_, ref after, ref budget) => Some(ExecutionCost {
+                write_length: after.write_length.saturating_sub(budget.write_length),
+                write_count: after.write_count.saturating_sub(budget.write_count),
+                read_length: after.read_length.saturating_sub(budget.read_length),
+                read_count: after.read_count.saturating_sub(budget.read_count),
+                runtime: after.runtime.saturating_sub(budget.runtime),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Make an `InvalidStacksTransaction` error.  If `quiet` is true, the transaction will be
+    /// silently dropped by the miner instead of being rejected with a warning.
+    pub fn invalid_tx(msg: String, quiet: bool) -> Error {
+        Error::InvalidStacksTransaction(msg, quiet)
+    }
+
+    /// If this is an `InvalidStacksTransaction` or `BadNonce` error, should the transaction be
+    /// quietly dropped (Some(true)) or loudly rejected (Some(false))?  Returns None for all other
+    /// errors.
+    pub fn is_quiet_rejection(&self) -> Option<bool> {
+        match *self {
+            Error::InvalidStacksTransaction(_, quiet) => Some(quiet),
+            Error::BadNonce(ref e) => Some(e.quiet),
+            _ => None,
+        }
+    }
+
+    /// Is this a transient error caused by database lock contention?
+    pub fn is_db_busy(&self) -> bool {
+        match *self {
+            Error::DBError(ref e) => e.is_busy(),
+            _ => false,
+        }
+    }
+}
+
+/// Compact reason codes sent back to peers and wallets when a transaction is rejected.
+/// The numeric values are part of the wire format and must not be changed.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxRejectCode {
+    BadNonce = 0x01,
+    FeeTooLow = 0x02,
+    PostConditionFailed = 0x03,
+    CostExceeded = 0x04,
+    BadSignature = 0x05,
+    ContractError = 0x06,
+    Other = 0xff,
+}
+
+impl TxRejectCode {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl From<&clarity_error> for TxRejectCode {
+    fn from(e: &clarity_error) -> TxRejectCode {
+        match e {
+            clarity_error::CostError(..) | clarity_error::MemoryBalanceExceeded(..) => {
+                TxRejectCode::CostExceeded
+            }
+            clarity_error::AbortedByCallback(..) => TxRejectCode::PostConditionFailed,
+            clarity_error::Analysis(_)
+            | clarity_error::Parse(_)
+            | clarity_error::Interpreter(_) => TxRejectCode::ContractError,
+            clarity_error::BadTransaction(_) => TxRejectCode::Other,
+        }
+    }
+}
+
+impl From<&MemPoolRejection> for TxRejectCode {
+    fn from(r: &MemPoolRejection) -> TxRejectCode {
+        match r {
+            MemPoolRejection::FeeTooLow(..) => TxRejectCode::FeeTooLow,
+            MemPoolRejection::BadNonces(_)
+            | MemPoolRejection::ConflictingNonceInMempool
+            | MemPoolRejection::TooMuchChaining { .. } => TxRejectCode::BadNonce,
+            MemPoolRejection::NoSuchContract
+            | MemPoolRejection::NoSuchPublicFunction
+            | MemPoolRejection::BadFunctionArgument(_)
+            | MemPoolRejection::ContractAlreadyExists(_) => TxRejectCode::ContractError,
+            MemPoolRejection::FailedToValidate(ref e) => TxRejectCode::from(e),
+            _ => TxRejectCode::Other,
+        }
+    }
+}
+
+impl From<&Error> for TxRejectCode {
+    fn from(e: &Error) -> TxRejectCode {
+        match e {
+            Error::InvalidFee => TxRejectCode::FeeTooLow,
+            Error::BadNonce(..) => TxRejectCode::BadNonce,
+            Error::PostConditionFailed(_) => TxRejectCode::PostConditionFailed,
+            Error::CostOverflowError(..) | Error::BlockCostExceeded => TxRejectCode::CostExceeded,
+            Error::IncompatibleSpendingConditionError
+            | Error::NetError(net_error::VerifyingError(_)) => TxRejectCode::BadSignature,
+            Error::ClarityError(ref e) => TxRejectCode::from(e),
+            Error::MemPoolError(ref r) => TxRejectCode::from(r.as_ref()),
+            _ => TxRejectCode::Other,
+        }
+    }
+}
+
 impl Txid {
     /// A Stacks transaction ID is a sha512/256 hash (not a double-sha256 hash)
     pub fn from_stacks_tx(txdata: &[u8]) -> Txid {
@@ -921,8 +1275,522 @@ pub mod test {
     use util::hash::*;
     use util::log;
 
+    use vm::errors::RuntimeErrorType;
     use vm::representations::{ClarityName, ContractName};
 
+    use super::Error;
+
+    #[test]
+    fn tx_reject_codes() {
+        let nonce_err = Error::from((
+            TransactionNonceMismatch {
+                expected: 1,
+                actual: 2,
+                txid: Txid([0u8; 32]),
+                principal: PrincipalData::Standard(StandardPrincipalData(1, [2u8; 20])),
+                is_origin: true,
+                quiet: false,
+            },
+            (),
+        ));
+        assert_eq!(TxRejectCode::from(&nonce_err), TxRejectCode::BadNonce);
+        assert_eq!(nonce_err.is_quiet_rejection(), Some(false));
+        assert_eq!(nonce_err.error_code(), 28);
+        // only the variant decides the code, not the wording of the message
+        assert_eq!(
+            TxRejectCode::from(&Error::InvalidStacksTransaction(
+                "Bad nonce: made up".to_string(),
+                false
+            )),
+            TxRejectCode::Other
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::InvalidFee),
+            TxRejectCode::FeeTooLow
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::PostConditionFailed("nope".to_string())),
+            TxRejectCode::PostConditionFailed
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::CostOverflowError(
+                ExecutionCost::zero(),
+                ExecutionCost::max_value(),
+                ExecutionCost::zero()
+            )),
+            TxRejectCode::CostExceeded
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::NetError(net_error::VerifyingError(
+                "bad sig".to_string()
+            ))),
+            TxRejectCode::BadSignature
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::ClarityError(clarity_error::CostError(
+                ExecutionCost::max_value(),
+                ExecutionCost::zero(),
+                None
+            ))),
+            TxRejectCode::CostExceeded
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::ClarityError(clarity_error::Interpreter(
+                clarity_interpreter_error::Runtime(RuntimeErrorType::DivisionByZero, None)
+            ))),
+            TxRejectCode::ContractError
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::InvalidStacksTransaction(
+                "something else".to_string(),
+                false
+            )),
+            TxRejectCode::Other
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::NoSuchBlockError),
+            TxRejectCode::Other
+        );
+
+        // mempool rejections are mapped by their typed reason
+        assert_eq!(
+            TxRejectCode::from(&Error::MemPoolError(Box::new(MemPoolRejection::FeeTooLow(
+                1, 180
+            )))),
+            TxRejectCode::FeeTooLow
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::MemPoolError(Box::new(MemPoolRejection::BadNonces(
+                TransactionNonceMismatch {
+                    expected: 1,
+                    actual: 2,
+                    txid: Txid([0u8; 32]),
+                    principal: PrincipalData::Standard(StandardPrincipalData(1, [2u8; 20])),
+                    is_origin: true,
+                    quiet: false,
+                }
+            )))),
+            TxRejectCode::BadNonce
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::MemPoolError(Box::new(
+                MemPoolRejection::FailedToValidate(Error::InvalidFee)
+            ))),
+            TxRejectCode::FeeTooLow
+        );
+        assert_eq!(
+            TxRejectCode::from(&Error::MemPoolError(Box::new(
+                MemPoolRejection::NotEnoughFunds(1, 0)
+            ))),
+            TxRejectCode::Other
+        );
+
+        assert_eq!(TxRejectCode::BadNonce.to_u8(), 0x01);
+        assert_eq!(TxRejectCode::Other.to_u8(), 0xff);
+    }
+
+    #[test]
+    fn chainstate_error_source_chain() {
+        use std::error::Error as _;
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let sqlite_err = conn
+            .execute("SELECT * FROM no_such_table", rusqlite::NO_PARAMS)
+            .unwrap_err();
+        let err = Error::DBError(db_error::SqliteError(sqlite_err));
+
+        let db_err = err
+            .source()
+            .expect("ChainstateError::DBError should have a source");
+        assert!(db_err.downcast_ref::<db_error>().is_some());
+
+        let root = db_err
+            .source()
+            .expect("DBError::SqliteError should have a source");
+        assert!(root.downcast_ref::<rusqlite::Error>().is_some());
+        assert!(root.to_string().contains("no_such_table"));
+    }
+
+    #[test]
+    fn chainstate_error_eq() {
+        assert_eq!(Error::BlockTooBigError, Error::BlockTooBigError);
+        assert_ne!(Error::BlockTooBigError, Error::BlockCostExceeded);
+        assert_eq!(
+            Error::InvalidStacksBlock("bad".to_string()),
+            Error::InvalidStacksBlock("bad".to_string())
+        );
+        assert_ne!(
+            Error::InvalidStacksBlock("bad".to_string()),
+            Error::InvalidStacksBlock("worse".to_string())
+        );
+        assert_eq!(
+            Error::ReadError(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            Error::ReadError(io::Error::from(io::ErrorKind::UnexpectedEof))
+        );
+    }
+
+    #[test]
+    fn chainstate_error_from_check_error() {
+        let check_err = ::vm::analysis::mem_type_check("(+ 1 u1)").unwrap_err();
+        let message = check_err.diagnostic.message.clone();
+        assert!(message.len() > 0);
+
+        let err = Error::from(check_err);
+        match err {
+            Error::ClarityError(clarity_error::Analysis(_)) => {}
+            ref x => panic!("Unexpected error: {:?}", x),
+        }
+
+        let json = err.into_json();
+        assert_eq!(json["reason"], json!("ClarityError"));
+        assert_eq!(json["reason_data"]["reason"], json!("Analysis"));
+        assert_eq!(
+            json["reason_data"]["reason_data"]["message"],
+            json!(message)
+        );
+    }
+
+    #[test]
+    fn chainstate_error_from_interpreter_failure() {
+        let err = Error::from(clarity_interpreter_failure::FailedToConstructAssetTable);
+        match err {
+            Error::ClarityError(clarity_error::Interpreter(
+                clarity_interpreter_error::Interpreter(
+                    clarity_interpreter_failure::FailedToConstructAssetTable,
+                ),
+            )) => {}
+            ref x => panic!("Unexpected error: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn chainstate_error_pox_json() {
+        let err = Error::PoxError(PoxError::AlreadyLocked {
+            amount_locked: u128::max_value(),
+            unlock_height: 100,
+        });
+        assert_eq!(err.to_string(), "Account has already locked STX for PoX");
+        let json = err.into_json();
+        assert_eq!(json["reason"], json!("PoxAlreadyLocked"));
+        assert_eq!(
+            json["reason_data"]["amount_locked"],
+            json!(u128::max_value().to_string())
+        );
+        assert_eq!(json["reason_data"]["unlock_height"], json!(100));
+
+        let err = Error::PoxError(PoxError::InsufficientBalance {
+            required: 1000,
+            available: 999,
+        });
+        assert_eq!(err.to_string(), "Not enough STX to lock");
+        let json = err.into_json();
+        assert_eq!(json["reason"], json!("PoxInsufficientBalance"));
+        assert_eq!(json["reason_data"]["required"], json!("1000"));
+        assert_eq!(json["reason_data"]["available"], json!("999"));
+
+        let err = Error::PoxError(PoxError::NoRewardCycle { burn_height: 42 });
+        assert_eq!(err.to_string(), "No such reward cycle");
+        let json = err.into_json();
+        assert_eq!(json["reason"], json!("PoxNoRewardCycle"));
+        assert_eq!(json["reason_data"]["burn_height"], json!(42));
+    }
+
+    #[test]
+    fn chainstate_error_mempool_json() {
+        let err = Error::MemPoolError(Box::new(MemPoolRejection::FeeTooLow(1, 180)));
+        let json = err.into_json();
+        assert_eq!(json["reason"], json!("MemPoolError"));
+        assert_eq!(json["reason_data"]["reason"], json!("FeeTooLow"));
+        assert_eq!(json["reason_data"]["reason_data"]["actual"], json!(1));
+        assert_eq!(json["reason_data"]["reason_data"]["expected"], json!(180));
+
+        let principal = PrincipalData::from(StacksAddress {
+            version: 1,
+            bytes: Hash160([0x11; 20]),
+        });
+        let err = Error::MemPoolError(Box::new(MemPoolRejection::BadNonces(
+            TransactionNonceMismatch {
+                expected: 3,
+                actual: 2,
+                txid: Txid([0x22; 32]),
+                principal: principal.clone(),
+                is_origin: true,
+                quiet: false,
+            },
+        )));
+        let json = err.into_json();
+        assert_eq!(json["reason_data"]["reason"], json!("BadNonce"));
+        assert_eq!(json["reason_data"]["reason_data"]["expected"], json!(3));
+        assert_eq!(json["reason_data"]["reason_data"]["actual"], json!(2));
+        assert_eq!(
+            json["reason_data"]["reason_data"]["principal"],
+            json!(principal.to_string())
+        );
+
+        // the reason has the same shape that the mempool reports to RPC clients
+        let rejection = MemPoolRejection::FeeTooLow(1, 180);
+        let (reason, reason_data) = rejection.reason();
+        let rpc_json = rejection.into_json(&Txid([0x33; 32]));
+        assert_eq!(rpc_json["reason"], json!(reason));
+        assert_eq!(rpc_json["reason_data"], reason_data.unwrap());
+
+        let err = Error::MemPoolError(Box::new(MemPoolRejection::Other(
+            "Not a coinbase transaction".to_string(),
+        )));
+        assert_eq!(format!("{}", &err), "Not a coinbase transaction");
+        let err = Error::MemPoolError(Box::new(MemPoolRejection::NoSuchContract));
+        assert_eq!(format!("{}", &err), "NoSuchContract");
+        assert_eq!(err.into_json()["reason_data"]["reason_data"], json!(null));
+    }
+
+    #[test]
+    fn chainstate_error_cost_overflow_json() {
+        let cost = |n: u64| ExecutionCost {
+            write_length: n,
+            write_count: n + 1,
+            read_length: n + 2,
+            read_count: n + 3,
+            runtime: n + 4,
+        };
+        let check_cost = |json: &serde_json::Value, n: u64| {
+            assert_eq!(json["write_length"].as_u64(), Some(n));
+            assert_eq!(json["write_count"].as_u64(), Some(n + 1));
+            assert_eq!(json["read_length"].as_u64(), Some(n + 2));
+            assert_eq!(json["read_count"].as_u64(), Some(n + 3));
+            assert_eq!(json["runtime"].as_u64(), Some(n + 4));
+        };
+
+        let json = Error::CostOverflowError(cost(0), cost(100), cost(50)).into_json();
+        assert_eq!(json["reason"], "CostOverflowError");
+        check_cost(&json["reason_data"]["cost_before"], 0);
+        check_cost(&json["reason_data"]["cost_after"], 100);
+        check_cost(&json["reason_data"]["budget"], 50);
+
+        let json = clarity_error::CostError(cost(100), cost(50), None).into_json();
+        check_cost(&json["reason_data"]["cost"], 100);
+        check_cost(&json["reason_data"]["budget"], 50);
+    }
+
+    #[test]
+    fn chainstate_error_cost_overrun() {
+        let before = ExecutionCost {
+            write_length: 10,
+            write_count: 10,
+            read_length: 10,
+            read_count: 10,
+            runtime: 10,
+        };
+        let after = ExecutionCost {
+            write_length: 150,
+            write_count: 20,
+            read_length: 100,
+            read_count: 5,
+            runtime: 1000,
+        };
+        let budget = ExecutionCost {
+            write_length: 100,
+            write_count: 100,
+            read_length: 100,
+            read_count: 100,
+            runtime: 400,
+        };
+        assert_eq!(
+            Error::CostOverflowError(before, after, budget).cost_overrun(),
+            Some(ExecutionCost {
+                write_length: 50,
+                write_count: 0,
+                read_length: 0,
+                read_count: 0,
+                runtime: 600,
+            })
+        );
+        assert_eq!(Error::BlockTooBigError.cost_overrun(), None);
+    }
+
+    #[test]
+    fn chainstate_error_cost_overflow() {
+        let before = ExecutionCost::zero();
+        let used = ExecutionCost {
+            write_length: 1,
+            write_count: 2,
+            read_length: 3,
+            read_count: 4,
+            runtime: 500,
+        };
+        let budget = ExecutionCost {
+            write_length: 1,
+            write_count: 2,
+            read_length: 3,
+            read_count: 4,
+            runtime: 400,
+        };
+
+        let mut store = ::vm::database::MemoryBackingStore::new();
+        let context = GlobalContext::new(
+            false,
+            store.as_clarity_db(),
+            ::vm::costs::LimitedCostTracker::new_free(),
+        );
+
+        assert_eq!(
+            Error::from_cost_error(
+                CostErrors::CostBalanceExceeded(used.clone(), budget.clone()),
+                before.clone(),
+                &context
+            ),
+            Error::cost_overflow(before, used, budget)
+        );
+    }
+
+    #[test]
+    fn chainstate_error_quiet_rejection() {
+        assert_eq!(
+            Error::invalid_tx("quiet".to_string(), true).is_quiet_rejection(),
+            Some(true)
+        );
+        assert_eq!(
+            Error::invalid_tx("loud".to_string(), false).is_quiet_rejection(),
+            Some(false)
+        );
+        assert_eq!(Error::InvalidFee.is_quiet_rejection(), None);
+    }
+
+    #[test]
+    fn chainstate_error_codes() {
+        let errors_and_codes = vec![
+            (Error::InvalidFee, 1),
+            (Error::InvalidStacksBlock("".to_string()), 2),
+            (
+                Error::InvalidStacksMicroblock("".to_string(), BlockHeaderHash([0; 32])),
+                3,
+            ),
+            (Error::InvalidStacksTransaction("".to_string(), false), 4),
+            (Error::PostConditionFailed("".to_string()), 5),
+            (Error::NoSuchBlockError, 6),
+            (Error::InvalidChainstateDB, 7),
+            (Error::BlockTooBigError, 8),
+            (Error::TransactionTooBigError, 9),
+            (Error::BlockCostExceeded, 10),
+            (Error::NoTransactionsToMine, 11),
+            (Error::MicroblockStreamTooLongError, 12),
+            (Error::IncompatibleSpendingConditionError, 13),
+            (
+                Error::CostOverflowError(
+                    ExecutionCost::zero(),
+                    ExecutionCost::zero(),
+                    ExecutionCost::zero(),
+                ),
+                14,
+            ),
+            (
+                Error::ClarityError(clarity_error::BadTransaction("".to_string())),
+                15,
+            ),
+            (Error::DBError(db_error::Corruption), 16),
+            (Error::NetError(net_error::ConnectionBroken), 17),
+            (Error::MARFError(marf_error::NotFoundError), 18),
+            (
+                Error::ReadError(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                19,
+            ),
+            (
+                Error::WriteError(io::Error::from(io::ErrorKind::WriteZero)),
+                20,
+            ),
+            (
+                Error::MemPoolError(Box::new(MemPoolRejection::Other("".to_string()))),
+                21,
+            ),
+            (
+                Error::PoxError(PoxError::AlreadyLocked {
+                    amount_locked: 1,
+                    unlock_height: 2,
+                }),
+                22,
+            ),
+            (
+                Error::PoxError(PoxError::InsufficientBalance {
+                    required: 2,
+                    available: 1,
+                }),
+                23,
+            ),
+            (
+                Error::PoxError(PoxError::NoRewardCycle { burn_height: 1 }),
+                24,
+            ),
+            (
+                Error::TimedOutOperation {
+                    op: "test",
+                    elapsed: Duration::from_secs(2),
+                    limit: Duration::from_secs(1),
+                },
+                25,
+            ),
+            (
+                Error::ContractAlreadyExists(QualifiedContractIdentifier::transient()),
+                26,
+            ),
+            (
+                Error::ContractTooLarge {
+                    size: 2,
+                    max_size: 1,
+                },
+                27,
+            ),
+            (
+                Error::BadNonce(TransactionNonceMismatch {
+                    expected: 1,
+                    actual: 2,
+                    txid: Txid([0u8; 32]),
+                    principal: PrincipalData::Standard(StandardPrincipalData(1, [2u8; 20])),
+                    is_origin: true,
+                    quiet: false,
+                }),
+                28,
+            ),
+        ];
+
+        let mut seen = std::collections::HashSet::new();
+        for (err, code) in errors_and_codes.iter() {
+            assert_eq!(err.error_code(), *code, "wrong code for {:?}", err);
+            assert!(seen.insert(err.error_code()), "duplicate code {}", code);
+            assert_eq!(err.into_json()["code"], json!(*code));
+        }
+    }
+
+    #[test]
+    fn chainstate_invalid_error_macros() {
+        let block_hash = BlockHeaderHash([0x11; 32]);
+        match invalid_block!(&block_hash, "bad {} at {}", "header", 3) {
+            Error::InvalidStacksBlock(msg) => assert_eq!(
+                msg,
+                format!("Invalid block {}: bad header at 3", &block_hash)
+            ),
+            x => panic!("Expected InvalidStacksBlock, got {:?}", &x),
+        }
+
+        match invalid_microblock!(block_hash.clone(), "sequence gap") {
+            Error::InvalidStacksMicroblock(msg, hash) => {
+                assert_eq!(
+                    msg,
+                    format!("Invalid microblock {}: sequence gap", &block_hash)
+                );
+                assert_eq!(hash, block_hash);
+            }
+            x => panic!("Expected InvalidStacksMicroblock, got {:?}", &x),
+        }
+
+        let txid = Txid([0x22; 32]);
+        match invalid_tx!(&txid, "nonce {} too high", 5) {
+            Error::InvalidStacksTransaction(msg, quiet) => {
+                assert_eq!(msg, format!("Invalid tx {}: nonce 5 too high", &txid));
+                assert!(!quiet);
+            }
+            x => panic!("Expected InvalidStacksTransaction, got {:?}", &x),
+        }
+    }
+
     /// Make a representative of each kind of transaction we support
     pub fn codec_all_transactions(
         version: &TransactionVersion,
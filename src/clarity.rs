@@ -559,7 +559,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
 
             let mut ast = friendly_expect(parse(&contract_id, &content), "Failed to parse program");
 
-            let contract_analysis = {
+            let mut contract_analysis = {
                 if args.len() >= 3 {
                     // use a persisted marf
                     let marf_kv = friendly_expect(
@@ -585,6 +585,12 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                 panic_test!();
             });
 
+            // lint warnings go to stderr, so they can't corrupt the --output_analysis JSON
+            analysis::lint_contract(&mut contract_analysis);
+            for diagnostic in contract_analysis.diagnostics.iter() {
+                eprintln!("{}", diagnostic);
+            }
+
             match args.last() {
                 Some(s) if s == "--output_analysis" => {
                     println!(
@@ -60,6 +60,10 @@ extern crate serde_json;
 #[macro_use]
 extern crate assert_json_diff;
 
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+
 #[cfg(feature = "monitoring_prom")]
 #[macro_use]
 pub extern crate prometheus;
@@ -14,11 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cell::Cell;
+use std::cmp;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::io;
 use std::io::prelude::*;
 use std::io::Read;
+use std::io::Seek;
+use std::marker::PhantomData;
 
 use burnchains::BurnchainHeaderHash;
 use burnchains::BurnchainView;
@@ -40,6 +44,7 @@ use chainstate::stacks::StacksPublicKey;
 use util::hash::DoubleSha256;
 use util::hash::Hash160;
 use util::hash::MerkleHashFunc;
+use util::hash::Sha256Sum;
 use util::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
 
 use net::db::LocalPeer;
@@ -56,6 +61,7 @@ use util::secp256k1::MESSAGE_SIGNATURE_ENCODED_SIZE;
 
 use util::log;
 use util::retry::BoundReader;
+use util::retry::LogReader;
 
 use util::hash::to_hex;
 
@@ -75,71 +81,316 @@ pub fn write_next<T: StacksMessageCodec, W: Write>(fd: &mut W, item: &T) -> Resu
     item.consensus_serialize(fd)
 }
 
+/// Write `items` as a length-prefixed array.  This is exactly how a `Vec<T>` is encoded, but
+/// doesn't need the items to be in a `Vec`.
+pub fn write_next_all<T: StacksMessageCodec, W: Write>(
+    fd: &mut W,
+    items: &[T],
+) -> Result<(), net_error> {
+    let len = items.len() as u32;
+    write_next(fd, &len)?;
+    for (i, item) in items.iter().enumerate() {
+        // only name the item if it fails, since this runs once per byte of a Vec<u8>
+        item.consensus_serialize(fd)
+            .map_err(|e| annotate_serialize_error(e, || format!("array item {}", i)))?;
+    }
+    Ok(())
+}
+
+/// Say which field of a composite value could not be encoded.  `field` is only called if `err`
+/// is a `SerializeError`.
+fn annotate_serialize_error<F: FnOnce() -> String>(err: net_error, field: F) -> net_error {
+    match err {
+        net_error::SerializeError(cause) => net_error::serialize_field(&field(), &cause),
+        e => e,
+    }
+}
+
+/// Write `item` as the field `field` of a composite value, so that a failure says which field
+/// could not be encoded.
+fn write_next_field<T: StacksMessageCodec, W: Write>(
+    fd: &mut W,
+    item: &T,
+    field: &str,
+) -> Result<(), net_error> {
+    item.consensus_serialize(fd)
+        .map_err(|e| annotate_serialize_error(e, || field.to_string()))
+}
+
 pub fn read_next<T: StacksMessageCodec, R: Read>(fd: &mut R) -> Result<T, net_error> {
     let item: T = T::consensus_deserialize(fd)?;
     Ok(item)
 }
 
+/// How many items a length-prefixed array may contain when it is decoded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CountMode {
+    /// The array may hold up to (and including) this many items.  `AtMost(0)` admits only the
+    /// empty array.
+    AtMost(u32),
+    /// The array must hold exactly this many items.
+    Exactly(u32),
+}
+
+thread_local!(static VEC_DESERIALIZE_DEPTH: Cell<u32> = const { Cell::new(0) });
+thread_local!(static VEC_DESERIALIZE_OFFSET: Cell<u64> = const { Cell::new(0) });
+
+/// Tracks how deeply nested the array currently being decoded on this thread is.  Entering a
+/// nested array increments the depth, and dropping the guard decrements it again.
+struct VecDepthGuard;
+
+impl VecDepthGuard {
+    fn enter() -> Result<VecDepthGuard, net_error> {
+        VEC_DESERIALIZE_DEPTH.with(|depth| {
+            if depth.get() >= MAX_NESTED_VEC_DEPTH {
+                return Err(net_error::DeserializeError(format!(
+                    "Arrays are nested too deeply (max depth is {})",
+                    MAX_NESTED_VEC_DEPTH
+                )));
+            }
+            depth.set(depth.get() + 1);
+            Ok(VecDepthGuard)
+        })
+    }
+
+    /// Is this the outermost array being decoded on this thread?
+    fn is_outermost(&self) -> bool {
+        VEC_DESERIALIZE_DEPTH.with(|depth| depth.get() == 1)
+    }
+}
+
+/// Reader for the outermost array being decoded.  It counts every byte it passes on in
+/// `VEC_DESERIALIZE_OFFSET`, so that nested arrays (which read through it) can report offsets
+/// from the start of the outermost array instead of from the start of their own.
+struct VecOffsetReader<'a> {
+    fd: &'a mut dyn Read,
+}
+
+impl<'a> VecOffsetReader<'a> {
+    fn new(fd: &'a mut dyn Read) -> VecOffsetReader<'a> {
+        VEC_DESERIALIZE_OFFSET.with(|offset| offset.set(0));
+        VecOffsetReader { fd }
+    }
+}
+
+impl<'a> Read for VecOffsetReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let num_read = self.fd.read(buf)?;
+        VEC_DESERIALIZE_OFFSET.with(|offset| offset.set(offset.get() + num_read as u64));
+        Ok(num_read)
+    }
+}
+
+/// How many bytes of the outermost array have been decoded so far on this thread.
+fn vec_deserialize_offset() -> u64 {
+    VEC_DESERIALIZE_OFFSET.with(|offset| offset.get())
+}
+
+impl Drop for VecDepthGuard {
+    fn drop(&mut self) {
+        VEC_DESERIALIZE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Say where in an array an error happened.  Truncation stays a `ReadError` with the same
+/// `io::ErrorKind`, so callers can still tell a short read apart from malformed data.
+fn annotate_vec_error(err: net_error, offset: u64, context: &str) -> net_error {
+    match err {
+        net_error::DeserializeError(detail) => net_error::deserialize_at(offset, context, &detail),
+        net_error::ReadError(ioe) => net_error::ReadError(io::Error::new(
+            ioe.kind(),
+            format!(
+                "Failed to decode {} at byte offset {}: {}",
+                context, offset, ioe
+            ),
+        )),
+        e => e,
+    }
+}
+
+/// Read the 4-byte length prefix of an array, and fail with `ArrayTooLong` if it is bigger than
+/// `max`.  Every length-prefixed array reader checks its length this way.
+pub fn read_length_prefix<R: Read>(fd: &mut R, max: u32) -> Result<u32, net_error> {
+    let len: u32 = read_next(fd)?;
+    if len > max {
+        return Err(net_error::ArrayTooLong);
+    }
+    Ok(len)
+}
+
 fn read_next_vec<T: StacksMessageCodec + Sized, R: Read>(
     fd: &mut R,
-    num_items: u32,
-    max_items: u32,
+    count_mode: CountMode,
 ) -> Result<Vec<T>, net_error> {
-    let len = u32::consensus_deserialize(fd)?;
-
-    if max_items > 0 {
-        if len > max_items {
-            // too many items
-            return Err(net_error::DeserializeError(format!(
-                "Array has too many items ({} > {}",
-                len, max_items
-            )));
-        }
+    let depth_guard = VecDepthGuard::enter()?;
+
+    // count bytes consumed, so errors can say where in the outermost array they happened.
+    // Nested arrays read through the outermost array's reader, so only that one counts.
+    // The reader is type-erased so that nested arrays don't nest reader types.
+    let mut offset_fd;
+    let mut fd: &mut dyn Read = if depth_guard.is_outermost() {
+        offset_fd = VecOffsetReader::new(fd);
+        &mut offset_fd
     } else {
-        if len != num_items {
-            // inexact item count
-            return Err(net_error::DeserializeError(format!(
-                "Array has incorrect number of items ({} != {})",
-                len, num_items
-            )));
+        fd
+    };
+    let array_offset = vec_deserialize_offset();
+    let len = match count_mode {
+        CountMode::AtMost(max_items) => read_length_prefix(&mut fd, max_items),
+        CountMode::Exactly(_) => read_next(&mut fd),
+    }
+    .map_err(|e| annotate_vec_error(e, array_offset, "array length"))?;
+
+    match count_mode {
+        CountMode::AtMost(_) => {}
+        CountMode::Exactly(num_items) => {
+            if len != num_items {
+                // inexact item count
+                return Err(net_error::deserialize_at(
+                    array_offset,
+                    "array length",
+                    &format!(
+                        "Array has incorrect number of items ({} != {})",
+                        len, num_items
+                    ),
+                ));
+            }
         }
     }
 
-    if (mem::size_of::<T>() as u128) * (len as u128) > MAX_MESSAGE_LEN as u128 {
-        return Err(net_error::DeserializeError(format!(
-            "Message occupies too many bytes (tried to allocate {}*{}={})",
-            mem::size_of::<T>() as u128,
-            len,
-            (mem::size_of::<T>() as u128) * (len as u128)
-        )));
-    }
-
-    let mut ret = Vec::with_capacity(len as usize);
-    for _i in 0..len {
-        let next_item = T::consensus_deserialize(fd)?;
-        ret.push(next_item);
+    // every item occupies at least this many bytes on the wire (and zero-sized items still
+    // cost a loop iteration, so count them as one byte)
+    let min_item_len = cmp::max(T::MIN_SERIALIZED_LEN, 1) as u128;
+    if min_item_len * (len as u128) > MAX_MESSAGE_LEN as u128 {
+        return Err(net_error::deserialize_at(
+            array_offset,
+            "array length",
+            &format!(
+                "Message occupies too many bytes ({} items of at least {} bytes each > {})",
+                len, min_item_len, MAX_MESSAGE_LEN
+            ),
+        ));
     }
 
+    // the items may be much bigger in memory than on the wire, so don't pre-allocate more
+    // than a message's worth of memory; the vec grows as items actually arrive.
+    let max_capacity = (MAX_MESSAGE_LEN as usize) / cmp::max(mem::size_of::<T>(), 1);
+    let mut ret = Vec::with_capacity(cmp::min(len as usize, max_capacity));
+    T::consensus_deserialize_items(&mut fd, len, &mut ret)?;
     Ok(ret)
 }
 
+/// Decode the `len` items of an array one at a time, appending them to `items`.  This is what
+/// `StacksMessageCodec::consensus_deserialize_items` does unless a type overrides it.
+pub fn read_next_items<T: StacksMessageCodec + Sized, R: Read>(
+    fd: &mut R,
+    len: u32,
+    items: &mut Vec<T>,
+) -> Result<(), net_error> {
+    for i in 0..len {
+        let item_offset = vec_deserialize_offset();
+        let next_item = T::consensus_deserialize(fd)
+            .map_err(|e| annotate_vec_error(e, item_offset, &format!("array item {}", i)))?;
+        items.push(next_item);
+    }
+    Ok(())
+}
+
 pub fn read_next_at_most<R: Read, T: StacksMessageCodec + Sized>(
     fd: &mut R,
     max_items: u32,
 ) -> Result<Vec<T>, net_error> {
-    read_next_vec::<T, R>(fd, 0, max_items)
+    read_next_vec::<T, R>(fd, CountMode::AtMost(max_items))
 }
 
 pub fn read_next_exact<R: Read, T: StacksMessageCodec + Sized>(
     fd: &mut R,
     num_items: u32,
 ) -> Result<Vec<T>, net_error> {
-    read_next_vec::<T, R>(fd, num_items, 0)
+    read_next_vec::<T, R>(fd, CountMode::Exactly(num_items))
+}
+
+/// Read the next item from `fd`, and report how many bytes it occupied.  Use this to step
+/// through a concatenation of encoded items.
+pub fn read_next_counted<T: StacksMessageCodec + Sized, R: Read + Seek>(
+    fd: &mut R,
+) -> Result<(T, u64), net_error> {
+    let start = fd.stream_position().map_err(net_error::ReadError)?;
+    let item = T::consensus_deserialize(fd)?;
+    let end = fd.stream_position().map_err(net_error::ReadError)?;
+    Ok((item, end - start))
+}
+
+/// Read the next item from `fd`, but consume no more than `max_bytes` bytes doing so.  If the
+/// item needs more bytes than that, this fails with `OverflowError`.
+pub fn read_next_bounded<T: StacksMessageCodec + Sized, R: Read>(
+    fd: &mut R,
+    max_bytes: u64,
+) -> Result<T, net_error> {
+    let mut bound_fd = BoundReader::from_reader(fd, max_bytes);
+    match T::consensus_deserialize(&mut bound_fd) {
+        Err(net_error::ReadError(ref ioe))
+            if ioe.kind() == io::ErrorKind::UnexpectedEof && bound_fd.num_read() >= max_bytes =>
+        {
+            Err(net_error::OverflowError(format!(
+                "Item is bigger than its {}-byte budget",
+                max_bytes
+            )))
+        }
+        res => res,
+    }
+}
+
+/// Is `fd` exhausted?  Use this after decoding a framed message to detect trailing bytes.
+/// At most one byte is consumed from `fd`.
+pub fn read_to_end_is_empty<R: Read>(fd: &mut R) -> Result<bool, net_error> {
+    let mut buf = [0u8; 1];
+    loop {
+        match fd.read(&mut buf) {
+            Ok(0) => return Ok(true),
+            Ok(_) => return Ok(false),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::Interrupted {
+                    return Err(net_error::ReadError(e));
+                }
+            }
+        }
+    }
+}
+
+/// Read exactly `num_items` items, and require that nothing follows them in `fd`.
+pub fn read_next_exact_final<R: Read, T: StacksMessageCodec + Sized>(
+    fd: &mut R,
+    num_items: u32,
+) -> Result<Vec<T>, net_error> {
+    let items = read_next_exact::<R, T>(fd, num_items)?;
+    if !read_to_end_is_empty(fd)? {
+        return Err(net_error::DeserializeError(
+            "Trailing bytes after array".to_string(),
+        ));
+    }
+    Ok(items)
+}
+
+/// Read exactly `num` raw bytes.  Refuses to allocate more than `MAX_MESSAGE_LEN` bytes.
+pub fn read_next_bytes<R: Read>(fd: &mut R, num: usize) -> Result<Vec<u8>, net_error> {
+    if (num as u128) > (MAX_MESSAGE_LEN as u128) {
+        return Err(net_error::DeserializeError(format!(
+            "Tried to read too many bytes ({} > {})",
+            num, MAX_MESSAGE_LEN
+        )));
+    }
+
+    let mut buf = vec![0u8; num];
+    fd.read_exact(&mut buf).map_err(net_error::ReadError)?;
+    Ok(buf)
 }
 
 macro_rules! impl_stacks_message_codec_for_int {
     ($typ:ty; $array:expr) => {
         impl StacksMessageCodec for $typ {
+            const MIN_SERIALIZED_LEN: usize = mem::size_of::<$typ>();
+
             fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
                 fd.write_all(&self.to_be_bytes())
                     .map_err(net_error::WriteError)
@@ -149,6 +400,37 @@ macro_rules! impl_stacks_message_codec_for_int {
                 fd.read_exact(&mut buf).map_err(net_error::ReadError)?;
                 Ok(<$typ>::from_be_bytes(buf))
             }
+            fn consensus_deserialize_items<R: Read>(
+                fd: &mut R,
+                len: u32,
+                items: &mut Vec<Self>,
+            ) -> Result<(), net_error> {
+                // the items are just their bytes, so read them all at once.  The buffer grows as
+                // the bytes arrive, so a bogus length can't make us allocate up front.
+                let item_len = mem::size_of::<$typ>();
+                let items_offset = vec_deserialize_offset();
+                let mut buf = vec![];
+                fd.take((len as u64) * (item_len as u64))
+                    .read_to_end(&mut buf)
+                    .map_err(net_error::ReadError)?;
+                if buf.len() < (len as usize) * item_len {
+                    let i = buf.len() / item_len;
+                    return Err(annotate_vec_error(
+                        net_error::ReadError(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        )),
+                        items_offset + (i * item_len) as u64,
+                        &format!("array item {}", i),
+                    ));
+                }
+                items.extend(buf.chunks_exact(item_len).map(|chunk| {
+                    let mut item_buf = $array;
+                    item_buf.copy_from_slice(chunk);
+                    <$typ>::from_be_bytes(item_buf)
+                }));
+                Ok(())
+            }
         }
     };
 }
@@ -158,6 +440,137 @@ impl_stacks_message_codec_for_int!(u16; [0; 2]);
 impl_stacks_message_codec_for_int!(u32; [0; 4]);
 impl_stacks_message_codec_for_int!(u64; [0; 8]);
 impl_stacks_message_codec_for_int!(i64; [0; 8]);
+impl_stacks_message_codec_for_int!(u128; [0; 16]);
+impl_stacks_message_codec_for_int!(i128; [0; 16]);
+
+/// Fixed-size byte arrays are encoded as their N bytes, with no length prefix.
+impl<const N: usize> StacksMessageCodec for [u8; N] {
+    const MIN_SERIALIZED_LEN: usize = N;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        fd.write_all(self).map_err(net_error::WriteError)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<[u8; N], net_error> {
+        let mut buf = [0u8; N];
+        fd.read_exact(&mut buf).map_err(net_error::ReadError)?;
+        Ok(buf)
+    }
+}
+
+/// Strings are encoded as a 4-byte length prefix followed by that many bytes of UTF-8.  Neither
+/// direction accepts strings longer than `MAX_MESSAGE_LEN` bytes.
+impl StacksMessageCodec for String {
+    const MIN_SERIALIZED_LEN: usize = 4;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        if self.len() > MAX_MESSAGE_LEN as usize {
+            return Err(net_error::SerializeError(format!(
+                "String is too long ({} > {})",
+                self.len(),
+                MAX_MESSAGE_LEN
+            )));
+        }
+        write_next(fd, &(self.len() as u32))?;
+        fd.write_all(self.as_bytes()).map_err(net_error::WriteError)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<String, net_error> {
+        let len: u32 = read_next(fd)?;
+        let bytes = read_next_bytes(fd, len as usize)?;
+        String::from_utf8(bytes)
+            .map_err(|e| net_error::DeserializeError(format!("Invalid UTF-8 string: {}", e)))
+    }
+}
+
+/// Booleans are encoded as a single byte, which must be 0 (false) or 1 (true).
+impl StacksMessageCodec for bool {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        let byte: u8 = if *self { 1 } else { 0 };
+        write_next(fd, &byte)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<bool, net_error> {
+        let byte: u8 = read_next(fd)?;
+        match byte {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(net_error::DeserializeError(format!(
+                "Invalid boolean encoding 0x{:02x}",
+                byte
+            ))),
+        }
+    }
+}
+
+/// VarInts are encoded as 1 to `VARINT_MAX_ENCODED_SIZE` bytes of LEB128.  Overlong encodings
+/// (trailing zero groups) and encodings that overflow a u64 are rejected.
+impl StacksMessageCodec for VarInt {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        let mut value = self.0;
+        loop {
+            let group = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                return write_next(fd, &group);
+            }
+            write_next(fd, &(group | 0x80))?;
+        }
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<VarInt, net_error> {
+        let mut value: u64 = 0;
+        for i in 0..VARINT_MAX_ENCODED_SIZE {
+            let byte: u8 = read_next(fd)?;
+            let group = (byte & 0x7f) as u64;
+            if i == VARINT_MAX_ENCODED_SIZE - 1 && group > 1 {
+                return Err(net_error::deserialize_at(
+                    i as u64,
+                    "varint",
+                    "Value does not fit in a u64",
+                ));
+            }
+            value |= group << (7 * i);
+            if byte & 0x80 == 0 {
+                if i > 0 && group == 0 {
+                    return Err(net_error::deserialize_at(
+                        i as u64,
+                        "varint",
+                        "Non-canonical encoding has a trailing zero byte",
+                    ));
+                }
+                return Ok(VarInt(value));
+            }
+        }
+        Err(net_error::deserialize_at(
+            VARINT_MAX_ENCODED_SIZE as u64,
+            "varint",
+            "Encoding is longer than any u64",
+        ))
+    }
+}
+
+impl StacksMessageCodec for StacksPublicKeyBuffer {
+    const MIN_SERIALIZED_LEN: usize = 33;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        fd.write_all(self.as_bytes()).map_err(net_error::WriteError)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<StacksPublicKeyBuffer, net_error> {
+        let mut buf = [0u8; 33];
+        fd.read_exact(&mut buf).map_err(net_error::ReadError)?;
+
+        // must be a compressed secp256k1 point
+        if buf[0] != 0x02 && buf[0] != 0x03 {
+            return Err(net_error::DeserializeError(format!(
+                "Invalid compressed public key prefix 0x{:02x}",
+                buf[0]
+            )));
+        }
+        Ok(StacksPublicKeyBuffer(buf))
+    }
+}
 
 impl StacksPublicKeyBuffer {
     pub fn from_public_key(pubkey: &Secp256k1PublicKey) -> StacksPublicKeyBuffer {
@@ -172,19 +585,35 @@ impl StacksPublicKeyBuffer {
             net_error::DeserializeError("Failed to decode Stacks public key".to_string())
         })
     }
+
+    /// Parse this buffer into a public key once, and get back a function that verifies
+    /// signatures against it.  Use this instead of `to_public_key()` + `verify()` when checking
+    /// many signatures from the same key.
+    pub fn verifier(
+        &self,
+    ) -> Result<impl Fn(&[u8], &MessageSignature) -> Result<bool, &'static str>, &'static str> {
+        let pubkey = Secp256k1PublicKey::from_slice(&self.0)?;
+        Ok(move |data_hash: &[u8], sig: &MessageSignature| pubkey.verify(data_hash, sig))
+    }
+
+    /// Recover the (compressed) public key that produced `sig` over the 32-byte `data_hash`.
+    pub fn recover(
+        data_hash: &[u8],
+        sig: &MessageSignature,
+    ) -> Result<StacksPublicKeyBuffer, &'static str> {
+        let pubkey = Secp256k1PublicKey::recover_to_pubkey(data_hash, sig)?;
+        Ok(StacksPublicKeyBuffer::from_public_key(&pubkey))
+    }
 }
 
 impl<T> StacksMessageCodec for Vec<T>
 where
     T: StacksMessageCodec + Sized,
 {
+    const MIN_SERIALIZED_LEN: usize = 4;
+
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
-        let len = self.len() as u32;
-        write_next(fd, &len)?;
-        for i in 0..self.len() {
-            write_next(fd, &self[i])?;
-        }
-        Ok(())
+        write_next_all(fd, self)
     }
 
     fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Vec<T>, net_error> {
@@ -192,6 +621,125 @@ where
     }
 }
 
+impl<T, const MAX: u32> StacksMessageCodec for BoundedVec<T, MAX>
+where
+    T: StacksMessageCodec + Sized,
+{
+    const MIN_SERIALIZED_LEN: usize = 4;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        if self.0.len() as u64 > MAX as u64 {
+            return Err(net_error::ArrayTooLong);
+        }
+        self.0.consensus_serialize(fd)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<BoundedVec<T, MAX>, net_error> {
+        let items = read_next_vec::<T, R>(fd, CountMode::AtMost(MAX))?;
+        Ok(BoundedVec(items))
+    }
+}
+
+/// The first `CHECKSUM_ENCODED_SIZE` bytes of the SHA-256 of `bytes`
+fn checksum_of(bytes: &[u8]) -> [u8; CHECKSUM_ENCODED_SIZE as usize] {
+    let mut checksum = [0u8; CHECKSUM_ENCODED_SIZE as usize];
+    checksum.copy_from_slice(
+        &Sha256Sum::from_data(bytes).as_bytes()[0..CHECKSUM_ENCODED_SIZE as usize],
+    );
+    checksum
+}
+
+impl<T> StacksMessageCodec for Checksummed<T>
+where
+    T: StacksMessageCodec + Sized,
+{
+    const MIN_SERIALIZED_LEN: usize = T::MIN_SERIALIZED_LEN + CHECKSUM_ENCODED_SIZE as usize;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        let mut bytes = vec![];
+        self.0.consensus_serialize(&mut bytes)?;
+        fd.write_all(&bytes).map_err(net_error::WriteError)?;
+        fd.write_all(&checksum_of(&bytes))
+            .map_err(net_error::WriteError)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Checksummed<T>, net_error> {
+        let (inner, bytes) = {
+            let mut log_fd = LogReader::from_reader(fd);
+            let inner = T::consensus_deserialize(&mut log_fd)?;
+            (inner, log_fd.log().concat())
+        };
+
+        let mut checksum = [0u8; CHECKSUM_ENCODED_SIZE as usize];
+        fd.read_exact(&mut checksum).map_err(net_error::ReadError)?;
+        if checksum != checksum_of(&bytes) {
+            return Err(net_error::InvalidMessage);
+        }
+        Ok(Checksummed(inner))
+    }
+}
+
+/// Pairs are encoded as their first item followed by their second, with no framing.
+impl<A, B> StacksMessageCodec for (A, B)
+where
+    A: StacksMessageCodec + Sized,
+    B: StacksMessageCodec + Sized,
+{
+    const MIN_SERIALIZED_LEN: usize = A::MIN_SERIALIZED_LEN + B::MIN_SERIALIZED_LEN;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        write_next_field(fd, &self.0, "tuple item 0")?;
+        write_next_field(fd, &self.1, "tuple item 1")?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<(A, B), net_error> {
+        let a: A = read_next(fd)?;
+        let b: B = read_next(fd)?;
+        Ok((a, b))
+    }
+}
+
+/// Triples are encoded like pairs: each item in order, with no framing.
+impl<A, B, C> StacksMessageCodec for (A, B, C)
+where
+    A: StacksMessageCodec + Sized,
+    B: StacksMessageCodec + Sized,
+    C: StacksMessageCodec + Sized,
+{
+    const MIN_SERIALIZED_LEN: usize =
+        A::MIN_SERIALIZED_LEN + B::MIN_SERIALIZED_LEN + C::MIN_SERIALIZED_LEN;
+
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        write_next_field(fd, &self.0, "tuple item 0")?;
+        write_next_field(fd, &self.1, "tuple item 1")?;
+        write_next_field(fd, &self.2, "tuple item 2")?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<(A, B, C), net_error> {
+        let a: A = read_next(fd)?;
+        let b: B = read_next(fd)?;
+        let c: C = read_next(fd)?;
+        Ok((a, b, c))
+    }
+}
+
+/// Zero-sized marker fields encode to nothing, so generic message structs can carry phantom type
+/// parameters without affecting their wire format.
+impl<T> StacksMessageCodec for PhantomData<T> {
+    const MIN_SERIALIZED_LEN: usize = 0;
+
+    fn consensus_serialize<W: Write>(&self, _fd: &mut W) -> Result<(), net_error> {
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(_fd: &mut R) -> Result<PhantomData<T>, net_error> {
+        Ok(PhantomData)
+    }
+}
+
 impl Preamble {
     /// Make an empty preamble with the given version and fork-set identifier, and payload length.
     pub fn new(
@@ -517,22 +1065,6 @@ impl StacksMessageCodec for PoxInvData {
     }
 }
 
-impl StacksMessageCodec for (ConsensusHash, BurnchainHeaderHash) {
-    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
-        write_next(fd, &self.0)?;
-        write_next(fd, &self.1)?;
-        Ok(())
-    }
-
-    fn consensus_deserialize<R: Read>(
-        fd: &mut R,
-    ) -> Result<(ConsensusHash, BurnchainHeaderHash), net_error> {
-        let consensus_hash: ConsensusHash = read_next(fd)?;
-        let burn_header_hash: BurnchainHeaderHash = read_next(fd)?;
-        Ok((consensus_hash, burn_header_hash))
-    }
-}
-
 impl StacksMessageCodec for BlocksAvailableData {
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
         write_next(fd, &self.available)?;
@@ -570,23 +1102,25 @@ impl BlocksAvailableData {
     }
 }
 
-impl StacksMessageCodec for (ConsensusHash, StacksBlock) {
+/// An entry of a `BlocksData` message.  Same wire format as `(ConsensusHash, StacksBlock)`, but
+/// decoding refuses to read a block bigger than `MAX_BLOCK_LEN`.
+struct BlocksDataEntry(ConsensusHash, StacksBlock);
+
+impl StacksMessageCodec for BlocksDataEntry {
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
         write_next(fd, &self.0)?;
         write_next(fd, &self.1)?;
         Ok(())
     }
 
-    fn consensus_deserialize<R: Read>(
-        fd: &mut R,
-    ) -> Result<(ConsensusHash, StacksBlock), net_error> {
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<BlocksDataEntry, net_error> {
         let ch: ConsensusHash = read_next(fd)?;
         let block = {
             let mut bound_read = BoundReader::from_reader(fd, MAX_BLOCK_LEN as u64);
             read_next(&mut bound_read)
         }?;
 
-        Ok((ch, block))
+        Ok(BlocksDataEntry(ch, block))
     }
 }
 
@@ -610,8 +1144,11 @@ impl StacksMessageCodec for BlocksData {
         let blocks: Vec<(ConsensusHash, StacksBlock)> = {
             // loose upper-bound
             let mut bound_read = BoundReader::from_reader(fd, MAX_MESSAGE_LEN as u64);
-            read_next_at_most::<_, (ConsensusHash, StacksBlock)>(&mut bound_read, BLOCKS_PUSHED_MAX)
-        }?;
+            read_next_at_most::<_, BlocksDataEntry>(&mut bound_read, BLOCKS_PUSHED_MAX)
+        }?
+        .into_iter()
+        .map(|BlocksDataEntry(ch, block)| (ch, block))
+        .collect();
 
         // only valid if there are no dups
         let mut present = HashSet::new();
@@ -1406,6 +1943,7 @@ impl ProtocolFamily for StacksP2P {
 pub mod test {
     use super::*;
 
+    use util::db::Error as db_error;
     use util::hash::hex_bytes;
     use util::secp256k1::*;
 
@@ -1526,6 +2064,25 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn codec_primitive_128_bit_types() {
+        check_codec_and_corruption::<u128>(&0, &vec![0x00; 16]);
+        check_codec_and_corruption::<u128>(&u128::max_value(), &vec![0xff; 16]);
+        check_codec_and_corruption::<u128>(
+            &0x000102030405060708090a0b0c0d0e0f,
+            &vec![
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+                0x0e, 0x0f,
+            ],
+        );
+
+        check_codec_and_corruption::<i128>(&0, &vec![0x00; 16]);
+        let mut i128_min_bytes = vec![0x00; 16];
+        i128_min_bytes[0] = 0x80;
+        check_codec_and_corruption::<i128>(&i128::min_value(), &i128_min_bytes);
+        check_codec_and_corruption::<i128>(&-1, &vec![0xff; 16]);
+    }
+
     #[test]
     fn codec_primitive_vector() {
         check_codec_and_corruption::<Vec<u8>>(&vec![], &vec![0x00, 0x00, 0x00, 0x00]);
@@ -1586,42 +2143,851 @@ pub mod test {
     }
 
     #[test]
-    fn codec_Preamble() {
-        let preamble = Preamble {
-            peer_version: 0x01020304,
-            network_id: 0x05060708,
-            seq: 0x090a0b0c,
-            burn_block_height: 0x00001122,
-            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
-            burn_stable_block_height: 0x00001111,
-            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
-            additional_data: 0x33333333,
-            signature: MessageSignature::from_raw(&vec![0x44; 65]),
-            payload_len: 0x000007ff,
-        };
-        let preamble_bytes: Vec<u8> = vec![
-            // peer_version
-            0x01, 0x02, 0x03, 0x04, // network_id
-            0x05, 0x06, 0x07, 0x08, // seq
-            0x09, 0x0a, 0x0b, 0x0c, // burn_block_height
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x22, // burn_block_hash
-            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
-            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
-            0x11, 0x11, 0x11, 0x11, // stable_burn_block_height
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x11, // stable_burn_block_hash
-            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
-            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
-            0x22, 0x22, 0x22, 0x22, // additional_data
-            0x33, 0x33, 0x33, 0x33, // signature
-            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
-            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
-            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
-            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
-            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, // payload_len
-            0x00, 0x00, 0x07, 0xff,
-        ];
+    fn codec_vec_length_bound() {
+        fn is_too_many_bytes<T: ::std::fmt::Debug>(res: &Result<T, net_error>) -> bool {
+            match res {
+                Err(net_error::DeserializeError(msg)) => msg.contains("too many bytes"),
+                _ => false,
+            }
+        }
 
-        assert_eq!(preamble_bytes.len() as u32, PREAMBLE_ENCODED_SIZE);
+        assert_eq!(<u8 as StacksMessageCodec>::MIN_SERIALIZED_LEN, 1);
+        assert_eq!(<u64 as StacksMessageCodec>::MIN_SERIALIZED_LEN, 8);
+        assert_eq!(<Vec<u64> as StacksMessageCodec>::MIN_SERIALIZED_LEN, 4);
+
+        // enough u64s to overflow a message, but not enough u8s
+        let len = MAX_MESSAGE_LEN / 8 + 1;
+        let bytes = len.serialize_to_vec();
+
+        let res = read_next::<Vec<u64>, _>(&mut &bytes[..]);
+        assert!(is_too_many_bytes(&res), "Unexpected result: {:?}", &res);
+
+        // passes the length check, and then runs out of data
+        let res = read_next::<Vec<u8>, _>(&mut &bytes[..]);
+        match res {
+            Err(net_error::ReadError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // an absurd length is rejected up front for any item type
+        let bytes = u32::max_value().serialize_to_vec();
+        let res = read_next::<Vec<u8>, _>(&mut &bytes[..]);
+        assert!(is_too_many_bytes(&res), "Unexpected result: {:?}", &res);
+        let res = read_next::<Vec<u64>, _>(&mut &bytes[..]);
+        assert!(is_too_many_bytes(&res), "Unexpected result: {:?}", &res);
+        let res = read_next::<Vec<PhantomData<u8>>, _>(&mut &bytes[..]);
+        assert!(is_too_many_bytes(&res), "Unexpected result: {:?}", &res);
+    }
+
+    #[test]
+    fn codec_write_next_all() {
+        let items: [u16; 3] = [0x0102, 0x0304, 0x0506];
+        let mut bytes = vec![];
+        write_next_all(&mut bytes, &items[..]).unwrap();
+
+        let mut vec_bytes = vec![];
+        write_next(&mut vec_bytes, &items.to_vec()).unwrap();
+        assert_eq!(bytes, vec_bytes);
+
+        let decoded: Vec<u16> = read_next(&mut &bytes[..]).unwrap();
+        assert_eq!(decoded, items.to_vec());
+
+        let mut empty_bytes = vec![];
+        write_next_all::<u16, _>(&mut empty_bytes, &[]).unwrap();
+        assert_eq!(empty_bytes, Vec::<u16>::new().serialize_to_vec());
+    }
+
+    #[test]
+    fn codec_serialize_to_shared_buffer() {
+        let mut buf = vec![];
+        0x01020304u32.consensus_serialize_to(&mut buf).unwrap();
+        vec![0x05u8, 0x06].consensus_serialize_to(&mut buf).unwrap();
+
+        let mut expected = 0x01020304u32.serialize_to_vec();
+        expected.append(&mut vec![0x05u8, 0x06].serialize_to_vec());
+        assert_eq!(
+            buf,
+            vec![0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x00, 0x02, 0x05, 0x06]
+        );
+        assert_eq!(buf, expected);
+    }
+
+    /// A self-similar type, so a peer can nest arrays arbitrarily deeply
+    #[derive(Debug)]
+    struct NestedVec(Vec<NestedVec>);
+
+    impl StacksMessageCodec for NestedVec {
+        fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+            write_next(fd, &self.0)
+        }
+        fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<NestedVec, net_error> {
+            let inner: Vec<NestedVec> = read_next(fd)?;
+            Ok(NestedVec(inner))
+        }
+    }
+
+    #[test]
+    fn codec_nested_vec_depth() {
+        // nesting up to the limit is fine
+        let mut ok_bytes = vec![];
+        for _ in 0..(MAX_NESTED_VEC_DEPTH - 1) {
+            ok_bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        }
+        ok_bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+        let nested: NestedVec = read_next(&mut &ok_bytes[..]).unwrap();
+        assert_eq!(nested.serialize_to_vec(), ok_bytes);
+
+        // pathologically deep nesting errors out instead of overflowing the stack
+        let mut bad_bytes = vec![];
+        for _ in 0..1_000_000 {
+            bad_bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        }
+        match read_next::<NestedVec, _>(&mut &bad_bytes[..]) {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // the depth counter unwinds after an error
+        let nested: NestedVec = read_next(&mut &ok_bytes[..]).unwrap();
+        assert_eq!(nested.serialize_to_vec(), ok_bytes);
+    }
+
+    #[test]
+    fn codec_read_next_bytes() {
+        let bytes = vec![0x01, 0x02, 0x03, 0x04];
+
+        // exact read
+        let mut fd = &bytes[..];
+        assert_eq!(read_next_bytes(&mut fd, 3).unwrap(), vec![0x01, 0x02, 0x03]);
+        assert_eq!(fd, &[0x04]);
+        assert_eq!(
+            read_next_bytes(&mut &bytes[..], 0).unwrap(),
+            Vec::<u8>::new()
+        );
+
+        // short read
+        match read_next_bytes(&mut &bytes[..], 5) {
+            Err(net_error::ReadError(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // over the limit
+        match read_next_bytes(&mut &bytes[..], (MAX_MESSAGE_LEN as usize) + 1) {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_byte_arrays() {
+        let bytes_20 = [0x11u8; 20];
+        check_codec_and_corruption::<[u8; 20]>(&bytes_20, &bytes_20.to_vec());
+
+        let mut bytes_33 = [0u8; 33];
+        for i in 0..33 {
+            bytes_33[i] = i as u8;
+        }
+        check_codec_and_corruption::<[u8; 33]>(&bytes_33, &bytes_33.to_vec());
+
+        // EOF
+        match <[u8; 33]>::consensus_deserialize(&mut &bytes_20[..]) {
+            Err(net_error::ReadError(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            x => panic!("Expected ReadError, got {:?}", &x),
+        }
+
+        // byte arrays nest in arrays like any other type
+        let arrays = vec![bytes_20.clone(), [0x22u8; 20]];
+        let mut arrays_bytes = vec![0x00, 0x00, 0x00, 0x02];
+        arrays_bytes.extend_from_slice(&bytes_20);
+        arrays_bytes.extend_from_slice(&[0x22u8; 20]);
+        check_codec_and_corruption::<Vec<[u8; 20]>>(&arrays, &arrays_bytes);
+    }
+
+    #[test]
+    fn codec_string() {
+        check_codec_and_corruption::<String>(
+            &"hello".to_string(),
+            &vec![0x00, 0x00, 0x00, 0x05, 0x68, 0x65, 0x6c, 0x6c, 0x6f],
+        );
+        check_codec_and_corruption::<String>(&"".to_string(), &vec![0x00, 0x00, 0x00, 0x00]);
+
+        // multibyte UTF-8: the length counts bytes, not characters
+        let multibyte = "h\u{e9}\u{1F436}".to_string();
+        let mut multibyte_bytes = vec![0x00, 0x00, 0x00, 0x07];
+        multibyte_bytes.extend_from_slice(multibyte.as_bytes());
+        check_codec_and_corruption::<String>(&multibyte, &multibyte_bytes);
+
+        // invalid UTF-8
+        let invalid = vec![0x00, 0x00, 0x00, 0x02, 0xc3, 0x28];
+        assert!(check_deserialize(String::consensus_deserialize(
+            &mut &invalid[..]
+        )));
+
+        // too long
+        let too_long = (MAX_MESSAGE_LEN + 1).to_be_bytes();
+        assert!(check_deserialize(String::consensus_deserialize(
+            &mut &too_long[..]
+        )));
+    }
+
+    #[test]
+    fn codec_trailing_bytes() {
+        let mut bytes = vec![0x00, 0x00, 0x00, 0x02, 0x01, 0x02];
+        let items: Vec<u8> = read_next_exact_final(&mut &bytes[..], 2).unwrap();
+        assert_eq!(items, vec![0x01, 0x02]);
+
+        let mut fd = &bytes[..];
+        let _: Vec<u8> = read_next_exact(&mut fd, 2).unwrap();
+        assert!(read_to_end_is_empty(&mut fd).unwrap());
+
+        // trailing garbage
+        bytes.push(0xff);
+        let mut fd = &bytes[..];
+        let _: Vec<u8> = read_next_exact(&mut fd, 2).unwrap();
+        assert!(!read_to_end_is_empty(&mut fd).unwrap());
+
+        assert!(check_deserialize(read_next_exact_final::<_, u8>(
+            &mut &bytes[..],
+            2
+        )));
+    }
+
+    #[test]
+    fn codec_net_error_from_marf_error() {
+        use chainstate::stacks::index::Error as marf_error;
+
+        fn marf_lookup() -> Result<u64, marf_error> {
+            Err(marf_error::NotFoundError)
+        }
+        fn net_lookup() -> Result<u64, net_error> {
+            let value = marf_lookup()?;
+            Ok(value)
+        }
+
+        match net_lookup() {
+            Err(net_error::MARFError(marf_error::NotFoundError)) => {}
+            x => panic!("Expected MARFError(NotFoundError), got {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_net_error_already_connected_display() {
+        let nk = NeighborKey {
+            peer_version: 0x18000000,
+            network_id: 0x80000000,
+            addrbytes: PeerAddress::from_ipv4(127, 0, 0, 1),
+            port: 20444,
+        };
+        let msg = format!("{}", net_error::AlreadyConnected(3, nk));
+        assert!(msg.contains("id=3"), "{}", msg);
+        assert!(msg.contains("127.0.0.1:20444"), "{}", msg);
+    }
+
+    #[test]
+    fn codec_net_error_is_transient() {
+        assert!(net_error::TemporarilyDrained.is_transient());
+        assert!(net_error::RecvTimeout.is_transient());
+        assert!(net_error::ConnectionBroken.is_transient());
+        assert!(net_error::DBError(db_error::Busy).is_transient());
+        for kind in [
+            io::ErrorKind::WouldBlock,
+            io::ErrorKind::TimedOut,
+            io::ErrorKind::Interrupted,
+        ]
+        .iter()
+        {
+            assert!(net_error::ReadError(io::Error::from(*kind)).is_transient());
+            assert!(net_error::WriteError(io::Error::from(*kind)).is_transient());
+        }
+
+        assert!(!net_error::InvalidMessage.is_transient());
+        assert!(!net_error::InvalidHandshake.is_transient());
+        assert!(!net_error::PermanentlyDrained.is_transient());
+        assert!(!net_error::DeserializeError("bad".to_string()).is_transient());
+        assert!(!net_error::DBError(db_error::Corruption).is_transient());
+        assert!(
+            !net_error::ReadError(io::Error::from(io::ErrorKind::UnexpectedEof)).is_transient()
+        );
+        assert!(!net_error::WriteError(io::Error::from(io::ErrorKind::BrokenPipe)).is_transient());
+    }
+
+    #[test]
+    fn codec_client_error_http_status() {
+        assert_eq!(ClientError::Message("bad".to_string()).http_status(), 400);
+        assert_eq!(
+            ClientError::NotFound("/v2/nope".to_string()).http_status(),
+            404
+        );
+    }
+
+    #[test]
+    fn codec_pubkey_buffer_recover() {
+        let privkey = Secp256k1PrivateKey::from_hex(
+            "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001",
+        )
+        .unwrap();
+        let pubkey_buf =
+            StacksPublicKeyBuffer::from_public_key(&Secp256k1PublicKey::from_private(&privkey));
+        let data_hash = [0x42u8; 32];
+        let sig = privkey.sign(&data_hash).unwrap();
+
+        assert_eq!(
+            StacksPublicKeyBuffer::recover(&data_hash, &sig).unwrap(),
+            pubkey_buf
+        );
+
+        // a different message recovers a different key
+        assert_ne!(
+            StacksPublicKeyBuffer::recover(&[0x43u8; 32], &sig).unwrap(),
+            pubkey_buf
+        );
+
+        // malformed signature
+        assert!(StacksPublicKeyBuffer::recover(&data_hash, &MessageSignature([0xff; 65])).is_err());
+
+        // malformed data hash
+        assert!(StacksPublicKeyBuffer::recover(&data_hash[0..31], &sig).is_err());
+    }
+
+    #[test]
+    fn codec_pubkey_buffer_verifier() {
+        // sha256 hash of "hello world", signed by the key below
+        let data_hash =
+            hex_bytes("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9").unwrap();
+        let sig = MessageSignature::from_raw(&hex_bytes("00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6").unwrap());
+        let pubkey_buf = StacksPublicKeyBuffer::from_bytes(
+            &hex_bytes("0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219")
+                .unwrap(),
+        )
+        .unwrap();
+
+        let verify = pubkey_buf.verifier().unwrap();
+        assert!(verify(&data_hash, &sig).unwrap());
+        assert!(!verify(&[0x00; 32], &sig).unwrap());
+        assert!(verify(&data_hash[0..31], &sig).is_err());
+
+        // not a valid point
+        assert!(StacksPublicKeyBuffer([0xff; 33]).verifier().is_err());
+    }
+
+    #[test]
+    #[ignore]
+    fn codec_pubkey_buffer_verifier_benchmark() {
+        use util::get_epoch_time_ms;
+
+        let privkey = Secp256k1PrivateKey::new();
+        let pubkey_buf =
+            StacksPublicKeyBuffer::from_public_key(&Secp256k1PublicKey::from_private(&privkey));
+        let items: Vec<_> = (0..1000)
+            .map(|i| {
+                let data_hash = [(i % 256) as u8; 32];
+                let sig = privkey.sign(&data_hash).unwrap();
+                (data_hash, sig)
+            })
+            .collect();
+
+        let parse_each_start = get_epoch_time_ms();
+        for (data_hash, sig) in items.iter() {
+            let pubkey = pubkey_buf.to_public_key().unwrap();
+            assert!(pubkey.verify(data_hash, sig).unwrap());
+        }
+        let parse_each_end = get_epoch_time_ms();
+
+        let verifier_start = get_epoch_time_ms();
+        let verify = pubkey_buf.verifier().unwrap();
+        for (data_hash, sig) in items.iter() {
+            assert!(verify(data_hash, sig).unwrap());
+        }
+        let verifier_end = get_epoch_time_ms();
+
+        test_debug!(
+            "Verified {} signatures: {} ms parsing the key each time, {} ms with a verifier",
+            items.len(),
+            parse_each_end - parse_each_start,
+            verifier_end - verifier_start
+        );
+    }
+
+    #[test]
+    fn codec_deserialize_exact() {
+        let bytes = vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        assert_eq!(
+            u64::consensus_deserialize_exact(&bytes).unwrap(),
+            0x0102030405060708
+        );
+
+        let mut long_bytes = bytes.clone();
+        long_bytes.push(0x09);
+        match u64::consensus_deserialize_exact(&long_bytes) {
+            Err(net_error::DeserializeError(msg)) => {
+                assert_eq!(msg, "Trailing bytes after message: 8 of 9 consumed")
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        assert!(u64::consensus_deserialize_exact(&bytes[0..7]).is_err());
+    }
+
+    #[test]
+    fn codec_read_next_counted() {
+        let mut bytes = vec![];
+        0x0102030405060708u64
+            .consensus_serialize(&mut bytes)
+            .unwrap();
+        0x1112131415161718u64
+            .consensus_serialize(&mut bytes)
+            .unwrap();
+        vec![1u8, 2, 3].consensus_serialize(&mut bytes).unwrap();
+
+        let mut cursor = io::Cursor::new(&bytes);
+        let (first, first_len) = read_next_counted::<u64, _>(&mut cursor).unwrap();
+        assert_eq!(first, 0x0102030405060708);
+        assert_eq!(first_len, 8);
+
+        let (second, second_len) = read_next_counted::<u64, _>(&mut cursor).unwrap();
+        assert_eq!(second, 0x1112131415161718);
+        assert_eq!(second_len, 8);
+
+        let (third, third_len) = read_next_counted::<Vec<u8>, _>(&mut cursor).unwrap();
+        assert_eq!(third, vec![1, 2, 3]);
+        assert_eq!(third_len, 7);
+
+        assert!(read_next_counted::<u64, _>(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn codec_serialize_counted() {
+        let nested: Vec<Vec<u8>> = vec![vec![], vec![1], vec![2, 3, 4, 5]];
+        let mut buf = vec![];
+        let count = nested.consensus_serialize_counted(&mut buf).unwrap();
+        assert_eq!(count, nested.serialize_to_vec().len() as u64);
+        assert_eq!(buf, nested.serialize_to_vec());
+
+        // the count is of this message only, not of what was already in the writer
+        let pubkey_buf = StacksPublicKeyBuffer([0x02; 33]);
+        let count = pubkey_buf.consensus_serialize_counted(&mut buf).unwrap();
+        assert_eq!(count, 33);
+        assert_eq!(
+            buf.len() as u64,
+            nested.serialize_to_vec().len() as u64 + 33
+        );
+
+        let count = 0u64.consensus_serialize_counted(&mut io::sink()).unwrap();
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn codec_checksummed() {
+        let inner: Vec<u16> = vec![1, 2, 3];
+        let inner_bytes = inner.serialize_to_vec();
+        let mut bytes = inner_bytes.clone();
+        bytes.extend_from_slice(&Sha256Sum::from_data(&inner_bytes).as_bytes()[0..4]);
+        assert_eq!(
+            bytes.len(),
+            inner_bytes.len() + CHECKSUM_ENCODED_SIZE as usize
+        );
+
+        check_codec_and_corruption::<Checksummed<Vec<u16>>>(&Checksummed(inner.clone()), &bytes);
+
+        // flipping any single bit is detected: either the inner message no longer decodes, or
+        // the checksum no longer matches
+        for i in 0..bytes.len() {
+            let mut corrupt_bytes = bytes.clone();
+            corrupt_bytes[i] ^= 0x01;
+            match Checksummed::<Vec<u16>>::consensus_deserialize(&mut &corrupt_bytes[..]) {
+                Ok(msg) => panic!("Decoded corrupt message at byte {}: {:?}", i, &msg),
+                Err(_) => {}
+            }
+        }
+
+        // corrupting an item's value leaves the inner message decodable
+        let mut corrupt_bytes = bytes.clone();
+        corrupt_bytes[5] = 0xff;
+        match Checksummed::<Vec<u16>>::consensus_deserialize(&mut &corrupt_bytes[..]) {
+            Err(net_error::InvalidMessage) => {}
+            res => panic!("Expected InvalidMessage, got {:?}", &res),
+        }
+    }
+
+    #[test]
+    fn codec_checksummed_serialize_error() {
+        let mut bytes = vec![];
+        match Checksummed(Unserializable).consensus_serialize(&mut bytes) {
+            Err(net_error::SerializeError(msg)) => assert_eq!(msg, "cannot encode"),
+            res => panic!("Expected SerializeError, got {:?}", &res),
+        }
+        assert!(bytes.is_empty());
+
+        let mut bytes = vec![];
+        match Checksummed(vec![Unserializable]).consensus_serialize(&mut bytes) {
+            Err(net_error::SerializeError(msg)) => assert!(msg.contains("array item 0")),
+            res => panic!("Expected SerializeError, got {:?}", &res),
+        }
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn codec_bounded_vec() {
+        // at the limit: same wire format as a Vec
+        let at_limit: BoundedVec<u16, 3> = BoundedVec::new(vec![1, 2, 3]).unwrap();
+        let at_limit_bytes = vec![0x00, 0x00, 0x00, 0x03, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+        check_codec_and_corruption::<BoundedVec<u16, 3>>(&at_limit, &at_limit_bytes);
+        assert_eq!(
+            Vec::<u16>::consensus_deserialize(&mut &at_limit_bytes[..]).unwrap(),
+            *at_limit
+        );
+
+        let empty: BoundedVec<u16, 0> = BoundedVec::new(vec![]).unwrap();
+        check_codec_and_corruption::<BoundedVec<u16, 0>>(&empty, &vec![0x00, 0x00, 0x00, 0x00]);
+
+        // over the limit
+        let over_limit_bytes = vec![
+            0x00, 0x00, 0x00, 0x04, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04,
+        ];
+        match BoundedVec::<u16, 3>::consensus_deserialize(&mut &over_limit_bytes[..]) {
+            Err(net_error::ArrayTooLong) => {}
+            res => panic!("Expected ArrayTooLong, got {:?}", &res),
+        }
+        match BoundedVec::<u16, 3>::new(vec![1, 2, 3, 4]) {
+            Err(net_error::ArrayTooLong) => {}
+            res => panic!("Expected ArrayTooLong, got {:?}", &res),
+        }
+
+        let mut bytes = vec![];
+        let over_limit: BoundedVec<u16, 3> = BoundedVec(vec![1, 2, 3, 4]);
+        match over_limit.consensus_serialize(&mut bytes) {
+            Err(net_error::ArrayTooLong) => {}
+            res => panic!("Expected ArrayTooLong, got {:?}", &res),
+        }
+    }
+
+    #[test]
+    fn codec_bool() {
+        check_codec_and_corruption::<bool>(&true, &vec![0x01]);
+        check_codec_and_corruption::<bool>(&false, &vec![0x00]);
+
+        let res = bool::consensus_deserialize(&mut &[0x02u8][..]);
+        assert!(check_deserialize(res));
+    }
+
+    #[test]
+    fn codec_net_error_from_io_error() {
+        match net_error::from(io::Error::from(io::ErrorKind::UnexpectedEof)) {
+            net_error::PermanentlyDrained => {}
+            e => panic!("Expected PermanentlyDrained, got {:?}", &e),
+        }
+        match net_error::from(io::Error::from(io::ErrorKind::WouldBlock)) {
+            net_error::TemporarilyDrained => {}
+            e => panic!("Expected TemporarilyDrained, got {:?}", &e),
+        }
+        match net_error::from(io::Error::from(io::ErrorKind::ConnectionReset)) {
+            net_error::ReadError(e) => assert_eq!(e.kind(), io::ErrorKind::ConnectionReset),
+            e => panic!("Expected ReadError, got {:?}", &e),
+        }
+    }
+
+    #[test]
+    fn codec_deserialize_error_offsets() {
+        let good_key = StacksPublicKeyBuffer::from_public_key(&Secp256k1PublicKey::from_private(
+            &Secp256k1PrivateKey::new(),
+        ));
+        let mut bad_key_bytes = vec![0x11; 33];
+        bad_key_bytes[0] = 0x04;
+
+        // [[good], [good, bad]]
+        let mut bytes = vec![0x00, 0x00, 0x00, 0x02];
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        bytes.extend_from_slice(good_key.as_bytes());
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x02]);
+        bytes.extend_from_slice(good_key.as_bytes());
+        bytes.extend_from_slice(&bad_key_bytes);
+
+        match read_next::<Vec<Vec<StacksPublicKeyBuffer>>, _>(&mut &bytes[..]) {
+            Err(net_error::DeserializeError(msg)) => {
+                // outer item 1 starts after the outer length and the first inner array; the bad
+                // key starts after that, the inner length and one key.  Both offsets are from
+                // the start of the outer array.
+                assert!(msg.contains("array item 1 at byte offset 41"), "{}", &msg);
+                assert!(msg.contains("array item 1 at byte offset 78"), "{}", &msg);
+                assert!(msg.contains("prefix 0x04"), "{}", &msg);
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // truncation is still a short read, but says where the data ran out
+        let mut short_bytes = bytes.clone();
+        short_bytes.truncate(4 + 4 + 33 + 4 + 33 + 10);
+        match read_next::<Vec<Vec<StacksPublicKeyBuffer>>, _>(&mut &short_bytes[..]) {
+            Err(net_error::ReadError(e)) => {
+                assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+                let msg = e.to_string();
+                assert!(msg.contains("array item 1 at byte offset 41"), "{}", &msg);
+                assert!(msg.contains("array item 1 at byte offset 78"), "{}", &msg);
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // byte arrays are read in bulk, but still say which item ran out
+        let mut short_bytes = vec![0x00, 0x00, 0x00, 0x02];
+        short_bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x04, 0x01, 0x02, 0x03, 0x04]);
+        short_bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x04, 0x01, 0x02]);
+        match read_next::<Vec<Vec<u8>>, _>(&mut &short_bytes[..]) {
+            Err(net_error::ReadError(e)) => {
+                assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+                let msg = e.to_string();
+                assert!(msg.contains("array item 1 at byte offset 12"), "{}", &msg);
+                assert!(msg.contains("array item 2 at byte offset 18"), "{}", &msg);
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // length errors are reported at the start of the array
+        match read_next_exact::<_, u8>(&mut &[0x00, 0x00, 0x00, 0x02, 0x01, 0x02][..], 1) {
+            Err(net_error::DeserializeError(msg)) => {
+                assert!(msg.contains("array length at byte offset 0"), "{}", &msg)
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_tuples() {
+        check_codec_and_corruption::<(u32, u64)>(
+            &(0x01020304, 0x05060708090a0b0c),
+            &vec![
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+            ],
+        );
+        check_codec_and_corruption::<(u8, Vec<u16>)>(
+            &(0x01, vec![0x0203, 0x0405]),
+            &vec![0x01, 0x00, 0x00, 0x00, 0x02, 0x02, 0x03, 0x04, 0x05],
+        );
+        check_codec_and_corruption::<(u8, bool, u16)>(
+            &(0x01, true, 0x0203),
+            &vec![0x01, 0x01, 0x02, 0x03],
+        );
+
+        assert_eq!(<(u32, u64) as StacksMessageCodec>::MIN_SERIALIZED_LEN, 12);
+
+        // pairs nest in arrays like any other type
+        let pairs: Vec<(u8, Vec<u16>)> = vec![(0x01, vec![]), (0x02, vec![0x0304])];
+        let bytes = pairs.serialize_to_vec();
+        assert_eq!(
+            bytes,
+            vec![
+                0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01,
+                0x03, 0x04
+            ]
+        );
+        assert_eq!(
+            read_next::<Vec<(u8, Vec<u16>)>, _>(&mut &bytes[..]).unwrap(),
+            pairs
+        );
+    }
+
+    #[test]
+    fn codec_read_next_bounded() {
+        let items: Vec<u64> = vec![1, 2, 3];
+        let bytes = items.serialize_to_vec();
+        assert_eq!(bytes.len(), 28);
+
+        // fits exactly
+        assert_eq!(
+            read_next_bounded::<Vec<u64>, _>(&mut &bytes[..], 28).unwrap(),
+            items
+        );
+
+        // doesn't fit
+        match read_next_bounded::<Vec<u64>, _>(&mut &bytes[..], 20) {
+            Err(net_error::OverflowError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // a short read within the budget is still a short read
+        match read_next_bounded::<Vec<u64>, _>(&mut &bytes[0..20], 28) {
+            Err(net_error::ReadError(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_varint() {
+        let cases: Vec<(u64, Vec<u8>)> = vec![
+            (0, vec![0x00]),
+            (127, vec![0x7f]),
+            (128, vec![0x80, 0x01]),
+            (
+                u64::MAX,
+                vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            ),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(VarInt(value).serialize_to_vec(), bytes);
+            assert_eq!(
+                VarInt::consensus_deserialize(&mut &bytes[..]).unwrap(),
+                VarInt(value)
+            );
+        }
+    }
+
+    #[test]
+    fn codec_varint_non_canonical() {
+        let bad: Vec<Vec<u8>> = vec![
+            // overlong encodings of 0 and 1
+            vec![0x80, 0x00],
+            vec![0x81, 0x80, 0x00],
+            // overflows a u64
+            vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02],
+            // too many bytes
+            vec![0x80; 11],
+        ];
+        for bytes in bad {
+            match VarInt::consensus_deserialize(&mut &bytes[..]) {
+                Err(net_error::DeserializeError(_)) => {}
+                x => panic!("Unexpected result for {:?}: {:?}", &bytes, &x),
+            }
+        }
+    }
+
+    /// A type that always fails to encode
+    #[derive(Debug)]
+    struct Unserializable;
+
+    impl StacksMessageCodec for Unserializable {
+        fn consensus_serialize<W: Write>(&self, _fd: &mut W) -> Result<(), net_error> {
+            Err(net_error::SerializeError("cannot encode".to_string()))
+        }
+        fn consensus_deserialize<R: Read>(_fd: &mut R) -> Result<Unserializable, net_error> {
+            Ok(Unserializable)
+        }
+    }
+
+    #[test]
+    fn codec_serialize_error_fields() {
+        assert_eq!(
+            net_error::serialize_field("payload", "too big").to_string(),
+            "failed to serialize field `payload`: too big"
+        );
+
+        let mut buf = vec![];
+        match vec![(0x01u8, vec![]), (0x02u8, vec![Unserializable])].consensus_serialize(&mut buf) {
+            Err(net_error::SerializeError(msg)) => {
+                assert!(msg.contains("field `array item 1`"), "{}", &msg);
+                assert!(msg.contains("field `tuple item 1`"), "{}", &msg);
+                assert!(msg.contains("field `array item 0`"), "{}", &msg);
+                assert!(msg.ends_with("cannot encode"), "{}", &msg);
+            }
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_phantom_data() {
+        let marker: PhantomData<u64> = PhantomData;
+        assert_eq!(marker.serialize_to_vec(), Vec::<u8>::new());
+
+        let empty: Vec<u8> = vec![];
+        let decoded: PhantomData<u64> = read_next(&mut &empty[..]).unwrap();
+        assert_eq!(decoded, marker);
+
+        // nothing is consumed from the stream
+        let bytes = vec![0x01, 0x02];
+        let mut fd = &bytes[..];
+        let _: PhantomData<u64> = read_next(&mut fd).unwrap();
+        assert_eq!(fd.len(), 2);
+    }
+
+    #[test]
+    fn codec_read_length_prefix() {
+        let bytes = 3u32.serialize_to_vec();
+
+        // at the limit
+        let mut fd = &bytes[..];
+        assert_eq!(read_length_prefix(&mut fd, 3).unwrap(), 3);
+        assert!(fd.is_empty());
+
+        // over the limit
+        match read_length_prefix(&mut &bytes[..], 2) {
+            Err(net_error::ArrayTooLong) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // a length that would be negative as a signed count is just a very big length
+        let bytes = (-1i64 as u32).serialize_to_vec();
+        match read_length_prefix(&mut &bytes[..], u32::max_value() - 1) {
+            Err(net_error::ArrayTooLong) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // arrays share the same check
+        let bytes = vec![1u8, 2, 3].serialize_to_vec();
+        assert_eq!(
+            read_next_at_most::<_, u8>(&mut &bytes[..], 3).unwrap(),
+            vec![1, 2, 3]
+        );
+        match read_next_at_most::<_, u8>(&mut &bytes[..], 2) {
+            Err(net_error::ArrayTooLong) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_read_next_at_most_zero() {
+        // an empty array is admitted
+        let empty_bytes = vec![0x00, 0x00, 0x00, 0x00];
+        let decoded: Vec<u32> = read_next_at_most(&mut &empty_bytes[..], 0).unwrap();
+        assert_eq!(decoded, Vec::<u32>::new());
+
+        // a nonempty array is rejected
+        let nonempty_bytes = vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02];
+        match read_next_at_most::<_, u32>(&mut &nonempty_bytes[..], 0) {
+            Err(net_error::ArrayTooLong) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+
+        // exact-count mode still works
+        let decoded: Vec<u32> = read_next_exact(&mut &nonempty_bytes[..], 1).unwrap();
+        assert_eq!(decoded, vec![2]);
+        assert!(read_next_exact::<_, u32>(&mut &nonempty_bytes[..], 0).is_err());
+    }
+
+    #[test]
+    fn codec_Preamble() {
+        let preamble = Preamble {
+            peer_version: 0x01020304,
+            network_id: 0x05060708,
+            seq: 0x090a0b0c,
+            burn_block_height: 0x00001122,
+            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
+            burn_stable_block_height: 0x00001111,
+            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
+            additional_data: 0x33333333,
+            signature: MessageSignature::from_raw(&vec![0x44; 65]),
+            payload_len: 0x000007ff,
+        };
+        let preamble_bytes: Vec<u8> = vec![
+            // peer_version
+            0x01, 0x02, 0x03, 0x04, // network_id
+            0x05, 0x06, 0x07, 0x08, // seq
+            0x09, 0x0a, 0x0b, 0x0c, // burn_block_height
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x22, // burn_block_hash
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+            0x11, 0x11, 0x11, 0x11, // stable_burn_block_height
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x11, // stable_burn_block_hash
+            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
+            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
+            0x22, 0x22, 0x22, 0x22, // additional_data
+            0x33, 0x33, 0x33, 0x33, // signature
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, // payload_len
+            0x00, 0x00, 0x07, 0xff,
+        ];
+
+        assert_eq!(preamble_bytes.len() as u32, PREAMBLE_ENCODED_SIZE);
         check_codec_and_corruption::<Preamble>(&preamble, &preamble_bytes);
     }
 
@@ -2260,7 +3626,12 @@ pub mod test {
                 relayers: too_many_relayers.clone(),
                 payload: payload.clone(),
             };
-            assert!(check_deserialize_failure(&stacks_message_too_many_relayers));
+            match StacksMessage::consensus_deserialize(
+                &mut &stacks_message_too_many_relayers.serialize_to_vec()[..],
+            ) {
+                Err(net_error::ArrayTooLong) => {}
+                x => panic!("Unexpected result: {:?}", &x),
+            }
         }
     }
 
@@ -2295,6 +3666,72 @@ pub mod test {
 
             assert_eq!(pubkey, pubkey_2);
         }
+
+        // a compressed key parses and can verify signatures
+        let privkey = Secp256k1PrivateKey::new();
+        let sig = privkey.sign(&[0x55; 32]).unwrap();
+        let pubkey_buf =
+            StacksPublicKeyBuffer::from_public_key(&Secp256k1PublicKey::from_private(&privkey));
+        assert!(pubkey_buf
+            .to_public_key()
+            .unwrap()
+            .verify(&[0x55; 32], &sig)
+            .unwrap());
+
+        // all zeros is not a valid point
+        match StacksPublicKeyBuffer([0u8; 33]).to_public_key() {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn codec_stacks_public_key_buffer() {
+        let privkey = Secp256k1PrivateKey::new();
+        let pubkey_buf =
+            StacksPublicKeyBuffer::from_public_key(&Secp256k1PublicKey::from_private(&privkey));
+        let bytes = pubkey_buf.as_bytes().to_vec();
+        check_codec_and_corruption::<StacksPublicKeyBuffer>(&pubkey_buf, &bytes);
+
+        for prefix in [0x02, 0x03].iter() {
+            let mut bytes = vec![0x11; 33];
+            bytes[0] = *prefix;
+            let decoded: StacksPublicKeyBuffer = read_next(&mut &bytes[..]).unwrap();
+            assert_eq!(decoded.as_bytes().to_vec(), bytes);
+        }
+
+        // uncompressed-point prefix
+        let mut bad_bytes = vec![0x11; 33];
+        bad_bytes[0] = 0x04;
+        match read_next::<StacksPublicKeyBuffer, _>(&mut &bad_bytes[..]) {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Unexpected result: {:?}", &x),
+        }
+    }
+
+    #[test]
+    fn stacks_public_key_buffer_serde() {
+        let pubkey_hex = "0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219";
+        let pubkey_buf = StacksPublicKeyBuffer::from_hex(pubkey_hex).unwrap();
+
+        let json = serde_json::to_string(&pubkey_buf).unwrap();
+        assert_eq!(json, format!("\"{}\"", pubkey_hex));
+
+        let decoded: StacksPublicKeyBuffer = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, pubkey_buf);
+
+        // wrong length
+        assert!(serde_json::from_str::<StacksPublicKeyBuffer>(&format!(
+            "\"{}\"",
+            &pubkey_hex[0..64]
+        ))
+        .is_err());
+
+        // not hex
+        assert!(
+            serde_json::from_str::<StacksPublicKeyBuffer>(&format!("\"{}\"", "zz".repeat(33)))
+                .is_err()
+        );
     }
 
     #[test]
@@ -2309,4 +3746,100 @@ pub mod test {
         let short_block_bitvec = BlocksInvData::compress_bools(&short_block_flags);
         assert_eq!(short_block_bitvec, vec![0x05]);
     }
+
+    mod proptests {
+        use super::*;
+
+        use chainstate::stacks::{StacksBlock, StacksMicroblock, StacksTransaction};
+        use proptest::prelude::*;
+        use vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+        use vm::ContractName;
+
+        fn check_roundtrip<T: StacksMessageCodec + PartialEq + std::fmt::Debug>(
+            value: &T,
+        ) -> Result<(), TestCaseError> {
+            let bytes = value.serialize_to_vec();
+            let decoded = T::consensus_deserialize(&mut &bytes[..]).map_err(|e| {
+                TestCaseError::fail(format!("failed to decode {:?}: {:?}", value, &e))
+            })?;
+            prop_assert_eq!(&decoded, value);
+            Ok(())
+        }
+
+        fn pubkey_buffer() -> impl Strategy<Value = StacksPublicKeyBuffer> {
+            (any::<[u8; 32]>(), prop_oneof![Just(0x02u8), Just(0x03u8)]).prop_map(
+                |(body, prefix)| {
+                    let mut bytes = [0u8; 33];
+                    bytes[0] = prefix;
+                    bytes[1..].copy_from_slice(&body);
+                    StacksPublicKeyBuffer(bytes)
+                },
+            )
+        }
+
+        fn standard_principal() -> impl Strategy<Value = StandardPrincipalData> {
+            (0u8..32, any::<[u8; 20]>())
+                .prop_map(|(version, bytes)| StandardPrincipalData(version, bytes))
+        }
+
+        fn principal() -> impl Strategy<Value = PrincipalData> {
+            prop_oneof![
+                standard_principal().prop_map(PrincipalData::Standard),
+                (standard_principal(), "[a-zA-Z][a-zA-Z0-9_-]{0,39}").prop_map(|(issuer, name)| {
+                    PrincipalData::Contract(QualifiedContractIdentifier::new(
+                        issuer,
+                        ContractName::try_from(name).unwrap(),
+                    ))
+                }),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn codec_roundtrip_integers(
+                a in any::<u8>(),
+                b in any::<u16>(),
+                c in any::<u32>(),
+                d in any::<u64>(),
+                e in any::<i64>()
+            ) {
+                check_roundtrip(&a)?;
+                check_roundtrip(&b)?;
+                check_roundtrip(&c)?;
+                check_roundtrip(&d)?;
+                check_roundtrip(&e)?;
+            }
+
+            #[test]
+            fn codec_roundtrip_u64_vec(v in prop::collection::vec(any::<u64>(), 0..256)) {
+                check_roundtrip(&v)?;
+            }
+
+            #[test]
+            fn codec_roundtrip_pubkey_buffer(buf in pubkey_buffer()) {
+                check_roundtrip(&buf)?;
+            }
+
+            #[test]
+            fn codec_roundtrip_principal(p in principal()) {
+                check_roundtrip(&p)?;
+            }
+
+            #[test]
+            fn codec_deserialize_arbitrary_bytes(
+                bytes in prop::collection::vec(any::<u8>(), 0..1024)
+            ) {
+                // any of these may fail, but none of them may panic
+                let _ = u64::consensus_deserialize(&mut &bytes[..]);
+                let _ = Vec::<u64>::consensus_deserialize(&mut &bytes[..]);
+                let _ = Vec::<Vec<u8>>::consensus_deserialize(&mut &bytes[..]);
+                let _ = StacksPublicKeyBuffer::consensus_deserialize(&mut &bytes[..]);
+                let _ = PrincipalData::consensus_deserialize(&mut &bytes[..]);
+                let _ = StacksTransaction::consensus_deserialize(&mut &bytes[..]);
+                let _ = StacksMicroblock::consensus_deserialize(&mut &bytes[..]);
+                let _ = StacksBlock::consensus_deserialize(&mut &bytes[..]);
+                let _ = StacksMessage::consensus_deserialize(&mut &bytes[..]);
+            }
+        }
+    }
 }
@@ -94,6 +94,7 @@ use util::db::DBConn;
 use util::db::Error as db_error;
 
 use util::log;
+use util::retry::CountWriter;
 
 use util::secp256k1::MessageSignature;
 use util::secp256k1::Secp256k1PublicKey;
@@ -178,11 +179,11 @@ pub enum Error {
     /// No such neighbor
     NoSuchNeighbor,
     /// Failed to bind
-    BindError,
+    BindError(io::Error),
     /// Failed to poll
-    PollError,
+    PollError(io::Error),
     /// Failed to accept
-    AcceptError,
+    AcceptError(io::Error),
     /// Failed to register socket with poller
     RegisterError,
     /// Failed to query socket metadata
@@ -213,8 +214,8 @@ pub enum Error {
     ChainstateError(String),
     /// Catch-all for errors that a client should receive more information about
     ClientError(ClientError),
-    /// Coordinator hung up
-    CoordinatorClosed,
+    /// Coordinator hung up while the given operation was being carried out
+    CoordinatorClosed(&'static str),
     /// view of state is stale (e.g. from the sortition db)
     StaleView,
     /// Tried to connect to myself
@@ -232,8 +233,19 @@ pub enum ClientError {
     NotFound(String),
 }
 
+impl ClientError {
+    /// The HTTP status code to answer a request that failed with this error.  Anything other
+    /// than an unmatched path is the client's fault, so it gets a 400.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ClientError::Message(_) => 400,
+            ClientError::NotFound(_) => 404,
+        }
+    }
+}
+
 impl error::Error for ClientError {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         None
     }
 }
@@ -281,15 +293,17 @@ impl fmt::Display for Error {
             Error::InvalidHandshake => write!(f, "invalid handshake from remote peer"),
             Error::StaleNeighbor => write!(f, "neighbor is too far behind the chain tip"),
             Error::NoSuchNeighbor => write!(f, "no such neighbor"),
-            Error::BindError => write!(f, "Failed to bind to the given address"),
-            Error::PollError => write!(f, "Failed to poll"),
-            Error::AcceptError => write!(f, "Failed to accept connection"),
+            Error::BindError(ref e) => write!(f, "Failed to bind to the given address: {}", e),
+            Error::PollError(ref e) => write!(f, "Failed to poll: {}", e),
+            Error::AcceptError(ref e) => write!(f, "Failed to accept connection: {}", e),
             Error::RegisterError => write!(f, "Failed to register socket with poller"),
             Error::SocketError => write!(f, "Socket error"),
             Error::NotConnected => write!(f, "Not connected to peer network"),
             Error::PeerNotConnected => write!(f, "Remote peer is not connected to us"),
             Error::TooManyPeers => write!(f, "Too many peer connections open"),
-            Error::AlreadyConnected(ref _id, ref _nk) => write!(f, "Peer already connected"),
+            Error::AlreadyConnected(ref id, ref nk) => {
+                write!(f, "Peer already connected: id={}, neighbor={}", id, nk)
+            }
             Error::InProgress => write!(f, "Message already in progress"),
             Error::Denied => write!(f, "Peer is denied"),
             Error::NoDataUrl => write!(f, "No data URL available"),
@@ -299,7 +313,7 @@ impl fmt::Display for Error {
             Error::ClarityError(ref e) => fmt::Display::fmt(e, f),
             Error::MARFError(ref e) => fmt::Display::fmt(e, f),
             Error::ClientError(ref e) => write!(f, "ClientError: {}", e),
-            Error::CoordinatorClosed => write!(f, "Coordinator hung up"),
+            Error::CoordinatorClosed(op) => write!(f, "Coordinator hung up during '{}'", op),
             Error::StaleView => write!(f, "State view is stale"),
             Error::ConnectionCycle => write!(f, "Tried to connect to myself"),
             Error::NotFoundError => write!(f, "Requested data not found"),
@@ -308,7 +322,7 @@ impl fmt::Display for Error {
 }
 
 impl error::Error for Error {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::SerializeError(ref _s) => None,
             Error::ReadError(ref io) => Some(io),
@@ -339,9 +353,9 @@ impl error::Error for Error {
             Error::InvalidHandshake => None,
             Error::StaleNeighbor => None,
             Error::NoSuchNeighbor => None,
-            Error::BindError => None,
-            Error::PollError => None,
-            Error::AcceptError => None,
+            Error::BindError(ref e) => Some(e),
+            Error::PollError(ref e) => Some(e),
+            Error::AcceptError(ref e) => Some(e),
             Error::RegisterError => None,
             Error::SocketError => None,
             Error::NotConnected => None,
@@ -357,7 +371,7 @@ impl error::Error for Error {
             Error::ClientError(ref e) => Some(e),
             Error::ClarityError(ref e) => Some(e),
             Error::MARFError(ref e) => Some(e),
-            Error::CoordinatorClosed => None,
+            Error::CoordinatorClosed(_) => None,
             Error::StaleView => None,
             Error::ConnectionCycle => None,
             Error::NotFoundError => None,
@@ -377,6 +391,9 @@ impl From<chain_error> for Error {
             chain_error::InvalidStacksTransaction(s, _) => {
                 Error::ChainstateError(format!("Invalid stacks transaction: {}", s))
             }
+            chain_error::BadNonce(e) => {
+                Error::ChainstateError(format!("Invalid stacks transaction: {}", e))
+            }
             chain_error::PostConditionFailed(s) => {
                 Error::ChainstateError(format!("Postcondition failed: {}", s))
             }
@@ -397,12 +414,74 @@ impl From<db_error> for Error {
     }
 }
 
+impl From<marf_error> for Error {
+    fn from(e: marf_error) -> Error {
+        Error::MARFError(e)
+    }
+}
+
 impl From<rusqlite::Error> for Error {
     fn from(e: rusqlite::Error) -> Error {
         Error::DBError(db_error::SqliteError(e))
     }
 }
 
+/// Map an I/O error encountered while reading.  Running out of bytes is reported as
+/// `PermanentlyDrained`, and a read that would block is reported as `TemporarilyDrained`;
+/// anything else is a `ReadError`.  Callers that got the error while writing should construct
+/// `WriteError` explicitly instead of using this conversion.
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => Error::PermanentlyDrained,
+            io::ErrorKind::WouldBlock => Error::TemporarilyDrained,
+            _ => Error::ReadError(e),
+        }
+    }
+}
+
+impl Error {
+    /// Make a `DeserializeError` that says where the failure happened (in `context`), as a byte
+    /// offset.  Array errors count from the start of the outermost array being decoded, so
+    /// nested failures all report offsets into the same bytes.
+    pub fn deserialize_at(offset: u64, context: &str, detail: &str) -> Error {
+        Error::DeserializeError(format!(
+            "Failed to decode {} at byte offset {}: {}",
+            context, offset, detail
+        ))
+    }
+
+    /// Make a `SerializeError` that says which field of a composite value could not be encoded.
+    pub fn serialize_field(field: &str, cause: &str) -> Error {
+        Error::SerializeError(format!("failed to serialize field `{}`: {}", field, cause))
+    }
+
+    /// Is this error transient?  That is, could the operation that produced it succeed if it
+    /// were retried later (e.g. after a backoff), without anything else changing?
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::TemporarilyDrained
+            | Error::RecvTimeout
+            | Error::ConnectionBroken
+            | Error::ConnectionError
+            | Error::OutboxOverflow
+            | Error::InboxOverflow
+            | Error::FullHandle
+            | Error::InProgress
+            | Error::PeerThrottled
+            | Error::StaleView => true,
+            Error::DBError(ref e) => e.is_busy(),
+            Error::ReadError(ref e) | Error::WriteError(ref e) => {
+                let kind = e.kind();
+                kind == io::ErrorKind::WouldBlock
+                    || kind == io::ErrorKind::TimedOut
+                    || kind == io::ErrorKind::Interrupted
+            }
+            _ => false,
+        }
+    }
+}
+
 #[cfg(test)]
 impl PartialEq for Error {
     /// (make I/O errors comparable for testing purposes)
@@ -415,6 +494,9 @@ impl PartialEq for Error {
 
 /// Helper trait for various primitive types that make up Stacks messages
 pub trait StacksMessageCodec {
+    /// The fewest bytes any encoding of this type can occupy on the wire.  Array decoding uses
+    ///  this to reject lengths that could not possibly fit in a message before allocating.
+    const MIN_SERIALIZED_LEN: usize = 1;
     /// serialize implementors _should never_ error unless there is an underlying
     ///   failure in writing to the `fd`
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), Error>
@@ -423,6 +505,37 @@ pub trait StacksMessageCodec {
     fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Self, Error>
     where
         Self: Sized;
+    /// Decode the `len` items of an array whose length prefix has already been read, appending
+    ///  them to `items`.  Types that can decode many items at once more cheaply than one at a
+    ///  time (such as bytes) override this.
+    fn consensus_deserialize_items<R: Read>(
+        fd: &mut R,
+        len: u32,
+        items: &mut Vec<Self>,
+    ) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        codec::read_next_items(fd, len, items)
+    }
+    /// Serialize by appending to an existing buffer, so the buffer can be reused across
+    ///  many messages.  Existing contents of `buf` are left untouched.
+    fn consensus_serialize_to(&self, buf: &mut Vec<u8>) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        self.consensus_serialize(buf)
+    }
+    /// Serialize directly into `fd`, without buffering the whole encoding first, and return the
+    ///  number of bytes written.
+    fn consensus_serialize_counted<W: Write>(&self, fd: &mut W) -> Result<u64, Error>
+    where
+        Self: Sized,
+    {
+        let mut count_fd = CountWriter::from_writer(fd);
+        self.consensus_serialize(&mut count_fd)?;
+        Ok(count_fd.num_written())
+    }
     /// Convenience for serialization to a vec.
     ///  this function unwraps any underlying serialization error
     fn serialize_to_vec(&self) -> Vec<u8>
@@ -430,10 +543,26 @@ pub trait StacksMessageCodec {
         Self: Sized,
     {
         let mut bytes = vec![];
-        self.consensus_serialize(&mut bytes)
+        self.consensus_serialize_to(&mut bytes)
             .expect("BUG: serialization to buffer failed.");
         bytes
     }
+    /// Decode a value from `bytes`, and require that the whole slice was consumed.
+    fn consensus_deserialize_exact(bytes: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let mut fd = bytes;
+        let value = Self::consensus_deserialize(&mut fd)?;
+        if !fd.is_empty() {
+            return Err(Error::DeserializeError(format!(
+                "Trailing bytes after message: {} of {} consumed",
+                bytes.len() - fd.len(),
+                bytes.len()
+            )));
+        }
+        Ok(value)
+    }
 }
 
 /// A container for an IPv4 or IPv6 address.
@@ -610,6 +739,49 @@ impl_byte_array_newtype!(StacksPublicKeyBuffer, u8, 33);
 
 pub const STACKS_PUBLIC_KEY_ENCODED_SIZE: u32 = 33;
 
+/// A length-prefixed array that may hold at most `MAX` items.  It has the same wire format as a
+/// `Vec<T>`, but decoding fails with `ArrayTooLong` if the array claims to hold more than `MAX`
+/// items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundedVec<T, const MAX: u32>(pub Vec<T>);
+
+impl<T, const MAX: u32> BoundedVec<T, MAX> {
+    /// Wrap `items`, or fail with `ArrayTooLong` if there are more than `MAX` of them.
+    pub fn new(items: Vec<T>) -> Result<BoundedVec<T, MAX>, Error> {
+        if items.len() as u64 > MAX as u64 {
+            return Err(Error::ArrayTooLong);
+        }
+        Ok(BoundedVec(items))
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MAX: u32> std::ops::Deref for BoundedVec<T, MAX> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+/// A message followed by the first 4 bytes of the SHA-256 of its encoding.  Decoding fails with
+/// `InvalidMessage` if the checksum does not match, so corrupted records are detected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checksummed<T>(pub T);
+
+pub const CHECKSUM_ENCODED_SIZE: u32 = 4;
+
+/// An unsigned integer encoded in LEB128 form: 7 bits per byte, least-significant group first,
+/// with the high bit set on every byte but the last.  Small values take fewer bytes than a fixed
+/// `u64`.  Decoding only accepts the shortest encoding of each value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarInt(pub u64);
+
+/// The most bytes a `VarInt` can occupy (ceil(64 / 7)).
+pub const VARINT_MAX_ENCODED_SIZE: usize = 10;
+
 /// supported HTTP content types
 #[derive(Debug, Clone, PartialEq)]
 pub enum HttpContentType {
@@ -1498,6 +1670,10 @@ pub const MAX_PAYLOAD_LEN: u32 = 1 + 16 * 1024 * 1024;
 pub const MAX_MESSAGE_LEN: u32 =
     MAX_PAYLOAD_LEN + (PREAMBLE_ENCODED_SIZE + MAX_RELAYERS_LEN * RELAY_DATA_ENCODED_SIZE);
 
+// maximum number of length-prefixed arrays that may be nested within one another when decoding a
+// message.  This bounds the stack depth a peer can make us recurse to.
+pub const MAX_NESTED_VEC_DEPTH: u32 = 16;
+
 // maximum number of blocks that can be announced as available
 pub const BLOCKS_AVAILABLE_MAX_LEN: u32 = 32;
 
@@ -1515,6 +1691,8 @@ pub const BLOCKS_PUSHED_MAX: u32 = 32;
 macro_rules! impl_byte_array_message_codec {
     ($thing:ident, $len:expr) => {
         impl ::net::StacksMessageCodec for $thing {
+            const MIN_SERIALIZED_LEN: usize = $len as usize;
+
             fn consensus_serialize<W: std::io::Write>(
                 &self,
                 fd: &mut W,
@@ -1538,9 +1716,9 @@ impl_byte_array_message_codec!(BlockHeaderHash, 32);
 impl_byte_array_message_codec!(StacksBlockId, 32);
 impl_byte_array_message_codec!(MessageSignature, 65);
 impl_byte_array_message_codec!(PeerAddress, 16);
-impl_byte_array_message_codec!(StacksPublicKeyBuffer, 33);
 
 impl_byte_array_serde!(ConsensusHash);
+impl_byte_array_serde!(StacksPublicKeyBuffer);
 
 /// neighbor identifier
 #[derive(Clone, Eq, PartialOrd, Ord)]
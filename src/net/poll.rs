@@ -89,7 +89,7 @@ impl NetworkState {
     pub fn new(event_capacity: usize) -> Result<NetworkState, net_error> {
         let poll = mio::Poll::new().map_err(|e| {
             error!("Failed to initialize poller: {:?}", e);
-            net_error::BindError
+            net_error::BindError(e)
         })?;
 
         let events = mio::Events::with_capacity(event_capacity);
@@ -112,7 +112,7 @@ impl NetworkState {
         if !cfg!(test) {
             mio_net::TcpListener::bind(addr).map_err(|e| {
                 error!("Failed to bind to {:?}: {:?}", addr, e);
-                net_error::BindError
+                net_error::BindError(e)
             })
         } else {
             let mut backoff = 1000;
@@ -136,7 +136,7 @@ impl NetworkState {
                         }
                         _ => {
                             debug!("Failed to bind {:?}: {:?}", addr, &e);
-                            return Err(net_error::BindError);
+                            return Err(net_error::BindError(e));
                         }
                     },
                 }
@@ -159,7 +159,7 @@ impl NetworkState {
             )
             .map_err(|e| {
                 error!("Failed to register server socket: {:?}", &e);
-                net_error::BindError
+                net_error::BindError(e)
             })?;
 
         let network_server = NetworkServerState {
@@ -364,7 +364,7 @@ impl NetworkState {
             .poll(&mut self.events, Some(Duration::from_millis(timeout)))
             .map_err(|e| {
                 error!("Failed to poll: {:?}", &e);
-                net_error::PollError
+                net_error::PollError(e)
             })?;
 
         let mut poll_states = HashMap::new();
@@ -398,7 +398,7 @@ impl NetworkState {
                                     break;
                                 }
                                 _ => {
-                                    return Err(net_error::AcceptError);
+                                    return Err(net_error::AcceptError(e));
                                 }
                             },
                         };
@@ -488,6 +488,22 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_bind_error_message() {
+        let e = net_error::BindError(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            "Address already in use",
+        ));
+        let msg = format!("{}", &e);
+        assert!(msg.contains("Failed to bind"));
+        assert!(msg.contains("Address already in use"));
+
+        match e {
+            net_error::BindError(ref io_e) => assert_eq!(io_e.kind(), io::ErrorKind::AddrInUse),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     #[ignore]
     fn test_register_deregister() {
@@ -1001,7 +1001,7 @@ impl Relayer {
             );
             if let Some(coord_comms) = coord_comms {
                 if !coord_comms.announce_new_stacks_block() {
-                    return Err(net_error::CoordinatorClosed);
+                    return Err(net_error::CoordinatorClosed("announce new Stacks block"));
                 }
             }
         }
@@ -1609,6 +1609,14 @@ mod test {
     use util::sleep_ms;
     use util::test::*;
 
+    #[test]
+    fn test_coordinator_closed_message() {
+        let e = net_error::CoordinatorClosed("announce new Stacks block");
+        let msg = format!("{}", &e);
+        assert!(msg.contains("Coordinator hung up"));
+        assert!(msg.contains("announce new Stacks block"));
+    }
+
     #[test]
     fn test_relayer_stats_add_relyed_messages() {
         let mut relay_stats = RelayerStats::new();
@@ -37,6 +37,7 @@ use rusqlite::types::{
 };
 use rusqlite::Connection;
 use rusqlite::Error as sqlite_error;
+use rusqlite::ErrorCode;
 use rusqlite::Row;
 use rusqlite::Transaction;
 use rusqlite::TransactionBehavior;
@@ -91,6 +92,8 @@ pub enum Error {
     IOError(IOError),
     /// MARF index error
     IndexError(MARFError),
+    /// Database is locked by another connection; the operation may succeed if retried
+    Busy,
     /// Other error
     Other(String),
 }
@@ -112,13 +115,14 @@ impl fmt::Display for Error {
             Error::IOError(ref e) => fmt::Display::fmt(e, f),
             Error::SqliteError(ref e) => fmt::Display::fmt(e, f),
             Error::IndexError(ref e) => fmt::Display::fmt(e, f),
+            Error::Busy => write!(f, "Database is busy"),
             Error::Other(ref s) => fmt::Display::fmt(s, f),
         }
     }
 }
 
 impl error::Error for Error {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::NotImplemented => None,
             Error::NoDBError => None,
@@ -134,17 +138,48 @@ impl error::Error for Error {
             Error::SqliteError(ref e) => Some(e),
             Error::IOError(ref e) => Some(e),
             Error::IndexError(ref e) => Some(e),
+            Error::Busy => None,
             Error::Other(ref _s) => None,
         }
     }
 }
 
+impl Error {
+    /// Is this a transient error caused by another connection holding a lock on the database?
+    pub fn is_busy(&self) -> bool {
+        match *self {
+            Error::Busy => true,
+            Error::SqliteError(sqlite_error::SqliteFailure(ref e, _)) => match e.code {
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Does this error mean that the requested data does not exist?  A query that was expected
+    /// to return a row but returned none counts as not-found.
+    pub fn is_not_found(&self) -> bool {
+        match *self {
+            Error::NotFoundError => true,
+            Error::SqliteError(sqlite_error::QueryReturnedNoRows) => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<sqlite_error> for Error {
     fn from(e: sqlite_error) -> Error {
         Error::SqliteError(e)
     }
 }
 
+impl From<serde_error> for Error {
+    fn from(e: serde_error) -> Error {
+        Error::SerializationError(e)
+    }
+}
+
 impl From<MARFError> for Error {
     fn from(e: MARFError) -> Error {
         Error::IndexError(e)
@@ -746,3 +781,33 @@ impl<'a, C: Clone, T: MarfTrieId> Drop for IndexDBTx<'a, C, T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_not_found() {
+        assert!(Error::NotFoundError.is_not_found());
+        assert!(Error::SqliteError(sqlite_error::QueryReturnedNoRows).is_not_found());
+        assert!(Error::from(sqlite_error::QueryReturnedNoRows).is_not_found());
+
+        assert!(!Error::Corruption.is_not_found());
+        assert!(!Error::ExistsError.is_not_found());
+        assert!(!Error::SqliteError(sqlite_error::InvalidQuery).is_not_found());
+    }
+
+    #[test]
+    fn test_from_serde_error() {
+        fn parse(input: &str) -> Result<Vec<u64>, Error> {
+            let parsed: Vec<u64> = serde_json::from_str(input)?;
+            Ok(parsed)
+        }
+
+        assert_eq!(parse("[1, 2, 3]").unwrap(), vec![1, 2, 3]);
+        match parse("[1, 2, ") {
+            Err(Error::SerializationError(_)) => {}
+            res => panic!("Expected SerializationError, got {:?}", &res),
+        }
+    }
+}
@@ -414,6 +414,44 @@ macro_rules! impl_byte_array_serde {
     };
 }
 
+/// Construct a chainstate `Error::InvalidStacksBlock` whose message reads
+/// "Invalid block <block hash>: <reason>".
+#[allow(unused_macros)]
+macro_rules! invalid_block {
+    ($block_hash:expr, $($arg:tt)*) => {
+        ::chainstate::stacks::Error::InvalidStacksBlock(format!(
+            "Invalid block {}: {}",
+            $block_hash,
+            format!($($arg)*)
+        ))
+    };
+}
+
+/// Construct a chainstate `Error::InvalidStacksMicroblock` whose message reads
+/// "Invalid microblock <microblock hash>: <reason>".
+#[allow(unused_macros)]
+macro_rules! invalid_microblock {
+    ($mblock_hash:expr, $($arg:tt)*) => {{
+        let mblock_hash = $mblock_hash;
+        ::chainstate::stacks::Error::InvalidStacksMicroblock(
+            format!("Invalid microblock {}: {}", &mblock_hash, format!($($arg)*)),
+            mblock_hash,
+        )
+    }};
+}
+
+/// Construct a (non-quiet) chainstate `Error::InvalidStacksTransaction` whose message reads
+/// "Invalid tx <txid>: <reason>".
+#[allow(unused_macros)]
+macro_rules! invalid_tx {
+    ($txid:expr, $($arg:tt)*) => {
+        ::chainstate::stacks::Error::invalid_tx(
+            format!("Invalid tx {}: {}", $txid, format!($($arg)*)),
+            false,
+        )
+    };
+}
+
 // print debug statements while testing
 #[allow(unused_macros)]
 macro_rules! test_debug {
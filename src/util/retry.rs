@@ -146,6 +146,34 @@ impl<'a, R: Read> Read for LogReader<'a, R> {
     }
 }
 
+/// A Write that counts how many bytes were written through it
+pub struct CountWriter<'a, W: Write> {
+    fd: &'a mut W,
+    written: u64,
+}
+
+impl<'a, W: Write> CountWriter<'a, W> {
+    pub fn from_writer(fd: &'a mut W) -> CountWriter<'a, W> {
+        CountWriter { fd: fd, written: 0 }
+    }
+
+    pub fn num_written(&self) -> u64 {
+        self.written
+    }
+}
+
+impl<'a, W: Write> Write for CountWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let nw = self.fd.write(buf)?;
+        self.written += nw as u64;
+        Ok(nw)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.fd.flush()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
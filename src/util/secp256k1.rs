@@ -122,6 +122,21 @@ impl MessageSignature {
             Err(_) => None,
         }
     }
+
+    /// Is this a well-formed signature whose S value is in the lower half of the curve order?
+    /// For every valid signature there is a second, high-S one over the same message;
+    /// `PublicKey::verify` only accepts the low-S form, so signatures can't be malleated.
+    pub fn is_low_s(&self) -> bool {
+        match self.to_secp256k1_recoverable() {
+            Some(sig) => {
+                let sig_standard = sig.to_standard();
+                let mut sig_low_s = sig_standard.clone();
+                sig_low_s.normalize_s();
+                sig_low_s == sig_standard
+            }
+            None => false,
+        }
+    }
 }
 
 impl Secp256k1PublicKey {
@@ -345,6 +360,7 @@ impl PrivateKey for Secp256k1PrivateKey {
         bits
     }
 
+    /// Sign with a deterministic nonce, per RFC 6979 (libsecp256k1's default nonce function).
     fn sign(&self, data_hash: &[u8]) -> Result<MessageSignature, &'static str> {
         _secp256k1.with(|ctx| {
             let msg = LibSecp256k1Message::from_slice(data_hash).map_err(|_e| {
@@ -544,6 +560,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_multisig() {
+        let privkeys: Vec<_> = (0..3).map(|_| Secp256k1PrivateKey::new()).collect();
+        let pubkeys: Vec<_> = privkeys
+            .iter()
+            .map(|pk| Secp256k1PublicKey::from_private(pk))
+            .collect();
+        let data_hash = [0x33; 32];
+        let sigs: Vec<_> = privkeys
+            .iter()
+            .map(|pk| pk.sign(&data_hash).unwrap())
+            .collect();
+
+        // 2-of-3 with two signatures
+        assert!(Secp256k1PublicKey::verify_multisig(&pubkeys, &sigs[0..2], &data_hash, 2).unwrap());
+        assert!(Secp256k1PublicKey::verify_multisig(&pubkeys, &sigs, &data_hash, 3).unwrap());
+
+        // not enough signatures
+        assert!(
+            !Secp256k1PublicKey::verify_multisig(&pubkeys, &sigs[0..1], &data_hash, 2).unwrap()
+        );
+
+        // signatures over some other data don't count
+        assert!(
+            !Secp256k1PublicKey::verify_multisig(&pubkeys, &sigs[0..2], &[0x44; 32], 2).unwrap()
+        );
+
+        // the same signature twice only counts once
+        let dup_sigs = vec![sigs[0].clone(), sigs[0].clone()];
+        assert!(!Secp256k1PublicKey::verify_multisig(&pubkeys, &dup_sigs, &data_hash, 2).unwrap());
+
+        // malformed data hash
+        assert!(Secp256k1PublicKey::verify_multisig(&pubkeys, &sigs, &[0x33; 31], 2).is_err());
+    }
+
+    #[test]
+    fn test_sign_deterministic() {
+        let privkey_hex = "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001";
+        let privkey = Secp256k1PrivateKey::from_hex(privkey_hex).unwrap();
+        let data_hash = [0x11u8; 32];
+
+        let sig_1 = privkey.sign(&data_hash).unwrap();
+        let sig_2 = privkey.sign(&data_hash).unwrap();
+        assert_eq!(sig_1, sig_2);
+
+        // same key, loaded separately (i.e. as another node would)
+        let other_privkey = Secp256k1PrivateKey::from_hex(privkey_hex).unwrap();
+        assert_eq!(other_privkey.sign(&data_hash).unwrap(), sig_1);
+
+        // different data, different signature
+        assert_ne!(privkey.sign(&[0x22u8; 32]).unwrap(), sig_1);
+    }
+
+    #[test]
+    fn test_is_low_s() {
+        // order of the secp256k1 group, big-endian
+        let curve_order =
+            hex_bytes("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+
+        let privkey = Secp256k1PrivateKey::new();
+        let pubkey = Secp256k1PublicKey::from_private(&privkey);
+        let data_hash = [0x33u8; 32];
+        let sig = privkey.sign(&data_hash).unwrap();
+        assert!(sig.is_low_s());
+        assert!(pubkey.verify(&data_hash, &sig).unwrap());
+
+        // malleate the signature: S' = n - S, which recovers the same key with the other
+        // recovery ID
+        let mut high_s_sig = sig.clone();
+        high_s_sig.0[0] ^= 0x01;
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = (curve_order[i] as i16) - (sig.0[33 + i] as i16) - borrow;
+            borrow = if diff < 0 { 1 } else { 0 };
+            high_s_sig.0[33 + i] = (diff + 256 * borrow) as u8;
+        }
+        assert!(!high_s_sig.is_low_s());
+        assert_eq!(
+            pubkey.verify(&data_hash, &high_s_sig),
+            Err("Invalid signature: high-S")
+        );
+
+        // not decodable (bad recovery ID)
+        assert!(!MessageSignature([0xff; 65]).is_low_s());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let mut items = vec![];
+        for i in 0..100 {
+            let privkey = Secp256k1PrivateKey::new();
+            let data_hash = vec![i as u8; 32];
+            let sig = privkey.sign(&data_hash).unwrap();
+            items.push((Secp256k1PublicKey::from_private(&privkey), data_hash, sig));
+        }
+
+        assert!(Secp256k1PublicKey::verify_batch(&items).unwrap());
+        assert!(Secp256k1PublicKey::verify_batch(&[]).unwrap());
+
+        // one signature over the wrong data spoils the batch
+        let mut bad_items = items.clone();
+        bad_items[50].1 = vec![0xff; 32];
+        assert!(!Secp256k1PublicKey::verify_batch(&bad_items).unwrap());
+
+        // one signature from the wrong key spoils the batch
+        let mut bad_items = items.clone();
+        bad_items[99].0 = items[0].0.clone();
+        assert!(!Secp256k1PublicKey::verify_batch(&bad_items).unwrap());
+
+        // malformed data hashes are errors
+        let mut bad_items = items.clone();
+        bad_items[10].1 = vec![0x01; 31];
+        assert!(Secp256k1PublicKey::verify_batch(&bad_items).is_err());
+    }
+
     #[test]
     fn test_verify() {
         let _ctx: Secp256k1<secp256k1::All> = Secp256k1::new();
@@ -442,7 +442,7 @@ mod test {
 
         let mut contract_bytes = vec![s.len() as u8];
         contract_bytes.extend_from_slice(contract_str.as_bytes());
-        check_codec_and_corruption::<ContractName>(&contract_str, &clarity_bytes);
+        check_codec_and_corruption::<ContractName>(&contract_str, &contract_bytes);
     }
 
     #[test]
@@ -467,6 +467,34 @@ mod test {
         s_payload.extend_from_slice(&s_body);
 
         assert!(ContractName::consensus_deserialize(&mut &s_payload[..]).is_err());
+
+        // invalid characters are rejected on read
+        let s = vec![5u8, 0x66, 0x6f, 0x2e, 0x6f, 0x6f]; // "fo.oo"
+        match ContractName::consensus_deserialize(&mut &s[..]) {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Expected DeserializeError, got {:?}", &x),
+        }
+
+        // leading digits are rejected on read
+        let s = vec![5u8, 0x31, 0x6f, 0x6f, 0x6f, 0x6f]; // "1oooo"
+        match ContractName::consensus_deserialize(&mut &s[..]) {
+            Err(net_error::DeserializeError(_)) => {}
+            x => panic!("Expected DeserializeError, got {:?}", &x),
+        }
+
+        // too-long names are rejected on write
+        let long_name = ContractName::try_from("a".repeat(CONTRACT_MAX_NAME_LENGTH + 1)).unwrap();
+        let mut bytes = vec![];
+        match long_name.consensus_serialize(&mut bytes) {
+            Err(net_error::SerializeError(_)) => {}
+            x => panic!("Expected SerializeError, got {:?}", &x),
+        }
+        assert_eq!(bytes.len(), 0);
+
+        // the TryFrom<String> conversion applies the same character rules
+        assert!(ContractName::try_from("fo.oo".to_string()).is_err());
+        assert!(ContractName::try_from("1oooo".to_string()).is_err());
+        assert!(ContractName::try_from("foo-bar_baz".to_string()).is_ok());
     }
 
     #[test]
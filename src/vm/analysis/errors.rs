@@ -17,7 +17,7 @@
 use std::error;
 use std::fmt;
 use vm::costs::{CostErrors, ExecutionCost};
-use vm::diagnostic::{DiagnosableError, Diagnostic};
+use vm::diagnostic::{DiagnosableError, Diagnostic, ErrorSummary};
 use vm::representations::SymbolicExpression;
 use vm::types::{TupleTypeSignature, TypeSignature, Value};
 
@@ -118,6 +118,7 @@ pub enum CheckErrors {
     // contract-call errors
     NoSuchContract(String),
     NoSuchPublicFunction(String, String),
+    FunctionIsPrivate(String, String),
     PublicFunctionNotReadOnly(String, String),
     ContractAlreadyExists(String),
     ContractCallExpectName,
@@ -166,6 +167,7 @@ pub enum CheckErrors {
     UnexpectedTraitOrFieldReference,
     TraitBasedContractCallInReadOnly,
     ContractOfExpectsTrait,
+    UnusedTraitImport(String),
 
     // strings
     InvalidCharactersDetected,
@@ -261,6 +263,12 @@ impl error::Error for CheckErrors {
     }
 }
 
+impl From<&CheckError> for ErrorSummary {
+    fn from(err: &CheckError) -> Self {
+        ErrorSummary::new(err.err.name(), &err.diagnostic)
+    }
+}
+
 impl From<CheckErrors> for CheckError {
     fn from(err: CheckErrors) -> Self {
         CheckError::new(err)
@@ -298,6 +306,120 @@ fn formatted_expected_types(expected_types: &Vec<TypeSignature>) -> String {
     expected_types_joined
 }
 
+impl CheckErrors {
+    /// The name of this error variant, for tooling that classifies errors.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CheckErrors::CostOverflow => "CostOverflow",
+            CheckErrors::CostBalanceExceeded(..) => "CostBalanceExceeded",
+            CheckErrors::MemoryBalanceExceeded(..) => "MemoryBalanceExceeded",
+            CheckErrors::CostComputationFailed(..) => "CostComputationFailed",
+            CheckErrors::ValueTooLarge => "ValueTooLarge",
+            CheckErrors::ValueOutOfBounds => "ValueOutOfBounds",
+            CheckErrors::TypeSignatureTooDeep => "TypeSignatureTooDeep",
+            CheckErrors::ExpectedName => "ExpectedName",
+            CheckErrors::BadMatchOptionSyntax(..) => "BadMatchOptionSyntax",
+            CheckErrors::BadMatchResponseSyntax(..) => "BadMatchResponseSyntax",
+            CheckErrors::BadMatchInput(..) => "BadMatchInput",
+            CheckErrors::UnknownListConstructionFailure => "UnknownListConstructionFailure",
+            CheckErrors::ListTypesMustMatch => "ListTypesMustMatch",
+            CheckErrors::ConstructedListTooLarge => "ConstructedListTooLarge",
+            CheckErrors::TypeError(..) => "TypeError",
+            CheckErrors::TypeLiteralError(..) => "TypeLiteralError",
+            CheckErrors::TypeValueError(..) => "TypeValueError",
+            CheckErrors::NoSuperType(..) => "NoSuperType",
+            CheckErrors::InvalidTypeDescription => "InvalidTypeDescription",
+            CheckErrors::UnknownTypeName(..) => "UnknownTypeName",
+            CheckErrors::UnionTypeError(..) => "UnionTypeError",
+            CheckErrors::UnionTypeValueError(..) => "UnionTypeValueError",
+            CheckErrors::ExpectedLiteral => "ExpectedLiteral",
+            CheckErrors::ExpectedOptionalType(..) => "ExpectedOptionalType",
+            CheckErrors::ExpectedResponseType(..) => "ExpectedResponseType",
+            CheckErrors::ExpectedOptionalOrResponseType(..) => "ExpectedOptionalOrResponseType",
+            CheckErrors::ExpectedOptionalValue(..) => "ExpectedOptionalValue",
+            CheckErrors::ExpectedResponseValue(..) => "ExpectedResponseValue",
+            CheckErrors::ExpectedOptionalOrResponseValue(..) => "ExpectedOptionalOrResponseValue",
+            CheckErrors::CouldNotDetermineResponseOkType => "CouldNotDetermineResponseOkType",
+            CheckErrors::CouldNotDetermineResponseErrType => "CouldNotDetermineResponseErrType",
+            CheckErrors::UncheckedIntermediaryResponses => "UncheckedIntermediaryResponses",
+            CheckErrors::CouldNotDetermineMatchTypes => "CouldNotDetermineMatchTypes",
+            CheckErrors::TypeAlreadyAnnotatedFailure => "TypeAlreadyAnnotatedFailure",
+            CheckErrors::TypeAnnotationExpectedFailure => "TypeAnnotationExpectedFailure",
+            CheckErrors::CheckerImplementationFailure => "CheckerImplementationFailure",
+            CheckErrors::BadTokenName => "BadTokenName",
+            CheckErrors::DefineFTBadSignature => "DefineFTBadSignature",
+            CheckErrors::DefineNFTBadSignature => "DefineNFTBadSignature",
+            CheckErrors::NoSuchNFT(..) => "NoSuchNFT",
+            CheckErrors::NoSuchFT(..) => "NoSuchFT",
+            CheckErrors::BadTransferSTXArguments => "BadTransferSTXArguments",
+            CheckErrors::BadTransferFTArguments => "BadTransferFTArguments",
+            CheckErrors::BadTransferNFTArguments => "BadTransferNFTArguments",
+            CheckErrors::BadMintFTArguments => "BadMintFTArguments",
+            CheckErrors::BadBurnFTArguments => "BadBurnFTArguments",
+            CheckErrors::BadTupleFieldName => "BadTupleFieldName",
+            CheckErrors::ExpectedTuple(..) => "ExpectedTuple",
+            CheckErrors::NoSuchTupleField(..) => "NoSuchTupleField",
+            CheckErrors::EmptyTuplesNotAllowed => "EmptyTuplesNotAllowed",
+            CheckErrors::BadTupleConstruction => "BadTupleConstruction",
+            CheckErrors::TupleExpectsPairs => "TupleExpectsPairs",
+            CheckErrors::NoSuchDataVariable(..) => "NoSuchDataVariable",
+            CheckErrors::BadMapName => "BadMapName",
+            CheckErrors::NoSuchMap(..) => "NoSuchMap",
+            CheckErrors::DefineFunctionBadSignature => "DefineFunctionBadSignature",
+            CheckErrors::BadFunctionName => "BadFunctionName",
+            CheckErrors::BadMapTypeDefinition => "BadMapTypeDefinition",
+            CheckErrors::PublicFunctionMustReturnResponse(..) => "PublicFunctionMustReturnResponse",
+            CheckErrors::DefineVariableBadSignature => "DefineVariableBadSignature",
+            CheckErrors::ReturnTypesMustMatch(..) => "ReturnTypesMustMatch",
+            CheckErrors::CircularReference(..) => "CircularReference",
+            CheckErrors::NoSuchContract(..) => "NoSuchContract",
+            CheckErrors::NoSuchPublicFunction(..) => "NoSuchPublicFunction",
+            CheckErrors::FunctionIsPrivate(..) => "FunctionIsPrivate",
+            CheckErrors::PublicFunctionNotReadOnly(..) => "PublicFunctionNotReadOnly",
+            CheckErrors::ContractAlreadyExists(..) => "ContractAlreadyExists",
+            CheckErrors::ContractCallExpectName => "ContractCallExpectName",
+            CheckErrors::NoSuchBlockInfoProperty(..) => "NoSuchBlockInfoProperty",
+            CheckErrors::GetBlockInfoExpectPropertyName => "GetBlockInfoExpectPropertyName",
+            CheckErrors::NameAlreadyUsed(..) => "NameAlreadyUsed",
+            CheckErrors::NonFunctionApplication => "NonFunctionApplication",
+            CheckErrors::ExpectedListApplication => "ExpectedListApplication",
+            CheckErrors::ExpectedSequence(..) => "ExpectedSequence",
+            CheckErrors::MaxLengthOverflow => "MaxLengthOverflow",
+            CheckErrors::BadLetSyntax => "BadLetSyntax",
+            CheckErrors::BadSyntaxBinding => "BadSyntaxBinding",
+            CheckErrors::BadSyntaxExpectedListOfPairs => "BadSyntaxExpectedListOfPairs",
+            CheckErrors::MaxContextDepthReached => "MaxContextDepthReached",
+            CheckErrors::UndefinedFunction(..) => "UndefinedFunction",
+            CheckErrors::UndefinedVariable(..) => "UndefinedVariable",
+            CheckErrors::RequiresAtLeastArguments(..) => "RequiresAtLeastArguments",
+            CheckErrors::IncorrectArgumentCount(..) => "IncorrectArgumentCount",
+            CheckErrors::IfArmsMustMatch(..) => "IfArmsMustMatch",
+            CheckErrors::MatchArmsMustMatch(..) => "MatchArmsMustMatch",
+            CheckErrors::DefaultTypesMustMatch(..) => "DefaultTypesMustMatch",
+            CheckErrors::TooManyExpressions => "TooManyExpressions",
+            CheckErrors::IllegalOrUnknownFunctionApplication(..) => {
+                "IllegalOrUnknownFunctionApplication"
+            }
+            CheckErrors::UnknownFunction(..) => "UnknownFunction",
+            CheckErrors::TraitReferenceUnknown(..) => "TraitReferenceUnknown",
+            CheckErrors::TraitMethodUnknown(..) => "TraitMethodUnknown",
+            CheckErrors::ExpectedTraitIdentifier => "ExpectedTraitIdentifier",
+            CheckErrors::ImportTraitBadSignature => "ImportTraitBadSignature",
+            CheckErrors::TraitReferenceNotAllowed => "TraitReferenceNotAllowed",
+            CheckErrors::BadTraitImplementation(..) => "BadTraitImplementation",
+            CheckErrors::DefineTraitBadSignature => "DefineTraitBadSignature",
+            CheckErrors::UnexpectedTraitOrFieldReference => "UnexpectedTraitOrFieldReference",
+            CheckErrors::TraitBasedContractCallInReadOnly => "TraitBasedContractCallInReadOnly",
+            CheckErrors::ContractOfExpectsTrait => "ContractOfExpectsTrait",
+            CheckErrors::UnusedTraitImport(..) => "UnusedTraitImport",
+            CheckErrors::InvalidCharactersDetected => "InvalidCharactersDetected",
+            CheckErrors::InvalidSecp65k1Signature => "InvalidSecp65k1Signature",
+            CheckErrors::WriteAttemptedInReadOnly => "WriteAttemptedInReadOnly",
+            CheckErrors::AtBlockClosureMustBeReadOnly => "AtBlockClosureMustBeReadOnly",
+        }
+    }
+}
+
 impl DiagnosableError for CheckErrors {
     fn message(&self) -> String {
         match &self {
@@ -342,7 +464,14 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::CouldNotDetermineMatchTypes => format!("attempted to match on an (optional) or (response) type where either the some, ok, or err type is indeterminate. you may wish to use unwrap-panic or unwrap-err-panic instead."),
             CheckErrors::BadTupleFieldName => format!("invalid tuple field name"),
             CheckErrors::ExpectedTuple(type_signature) => format!("expecting tuple, found '{}'", type_signature),
-            CheckErrors::NoSuchTupleField(field_name, tuple_signature) => format!("cannot find field '{}' in tuple '{}'", field_name, tuple_signature),
+            CheckErrors::NoSuchTupleField(field_name, tuple_signature) => {
+                let field_names: Vec<&str> = tuple_signature.get_type_map().keys().map(|name| name.as_str()).collect();
+                if field_names.is_empty() {
+                    format!("cannot find field '{}' in tuple '{}'; the tuple has no fields", field_name, tuple_signature)
+                } else {
+                    format!("cannot find field '{}' in tuple '{}'; available: {}", field_name, tuple_signature, field_names.join(", "))
+                }
+            }
             CheckErrors::BadTupleConstruction => format!("invalid tuple syntax, expecting list of pair"),
             CheckErrors::TupleExpectsPairs => format!("invalid tuple syntax, expecting pair"),
             CheckErrors::NoSuchDataVariable(var_name) => format!("use of unresolved persisted variable '{}'", var_name),
@@ -361,6 +490,7 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::ReturnTypesMustMatch(type_1, type_2) => format!("detected two execution paths, returning two different expression types (got '{}' and '{}')", type_1, type_2),
             CheckErrors::NoSuchContract(contract_identifier) => format!("use of unresolved contract '{}'", contract_identifier),
             CheckErrors::NoSuchPublicFunction(contract_identifier, function_name) => format!("contract '{}' has no public function '{}'", contract_identifier, function_name),
+            CheckErrors::FunctionIsPrivate(contract_identifier, function_name) => format!("function '{}' in contract '{}' is private, and cannot be called from another contract", function_name, contract_identifier),
             CheckErrors::PublicFunctionNotReadOnly(contract_identifier, function_name) => format!("function '{}' in '{}' is not read-only", contract_identifier, function_name),
             CheckErrors::ContractAlreadyExists(contract_identifier) => format!("contract name '{}' conflicts with existing contract", contract_identifier),
             CheckErrors::ContractCallExpectName => format!("missing contract name for call"),
@@ -402,6 +532,7 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::DefineTraitBadSignature => format!("invalid trait definition"),
             CheckErrors::TraitReferenceNotAllowed => format!("trait references can not be stored"),
             CheckErrors::ContractOfExpectsTrait => format!("trait reference expected"),
+            CheckErrors::UnusedTraitImport(trait_name) => format!("trait <{}> is imported but never used", trait_name),
             CheckErrors::InvalidCharactersDetected => format!("invalid characters detected"),
             CheckErrors::InvalidSecp65k1Signature => format!("invalid seckp256k1 signature"),
             CheckErrors::TypeAlreadyAnnotatedFailure | CheckErrors::CheckerImplementationFailure => {
@@ -423,9 +554,44 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::TraitReferenceUnknown(_) => Some(format!(
                 "traits should be either defined, with define-trait, or imported, with use-trait."
             )),
+            CheckErrors::UnusedTraitImport(trait_name) => Some(format!(
+                "remove the unused import: (use-trait {} ...)",
+                trait_name
+            )),
             CheckErrors::NoSuchBlockInfoProperty(_) => Some(format!(
                 "properties available: time, header-hash, burnchain-header-hash, vrf-seed"
             )),
+            // the message already lists the available fields
+            CheckErrors::NoSuchTupleField(field_name, _) => Some(format!(
+                "check the spelling of '{}', or add it where the tuple is constructed",
+                field_name
+            )),
+            CheckErrors::IncorrectArgumentCount(expected_count, found_count) => {
+                if found_count > expected_count {
+                    Some(format!(
+                        "remove {} argument(s): this function takes exactly {}",
+                        found_count - expected_count,
+                        expected_count
+                    ))
+                } else {
+                    Some(format!(
+                        "add {} argument(s): this function takes exactly {}",
+                        expected_count - found_count,
+                        expected_count
+                    ))
+                }
+            }
+            CheckErrors::RequiresAtLeastArguments(expected_count, found_count) => Some(format!(
+                "add {} argument(s): this function takes at least {}",
+                expected_count.saturating_sub(*found_count),
+                expected_count
+            )),
+            CheckErrors::PublicFunctionMustReturnResponse(_) => Some(format!(
+                "wrap the returned value with (ok <value>) or (err <value>)"
+            )),
+            CheckErrors::ExpectedSequence(_) => Some(format!(
+                "sequence functions operate on values of type list, buff, string-ascii or string-utf8"
+            )),
             _ => None,
         }
     }
@@ -22,6 +22,7 @@ pub mod read_only_checker;
 pub mod trait_checker;
 pub mod type_checker;
 pub mod types;
+pub mod unused_trait_checker;
 
 pub use self::types::{AnalysisPass, ContractAnalysis};
 use vm::costs::LimitedCostTracker;
@@ -37,6 +38,7 @@ use self::contract_interface_builder::build_contract_interface;
 use self::read_only_checker::ReadOnlyChecker;
 use self::trait_checker::TraitChecker;
 use self::type_checker::TypeChecker;
+use self::unused_trait_checker::UnusedTraitChecker;
 
 pub fn mem_type_check(snippet: &str) -> CheckResult<(Option<TypeSignature>, ContractAnalysis)> {
     use vm::ast::parse;
@@ -51,7 +53,8 @@ pub fn mem_type_check(snippet: &str) -> CheckResult<(Option<TypeSignature>, Cont
         &mut analysis_db,
         false,
     )
-    .map(|x| {
+    .map(|mut x| {
+        lint_contract(&mut x);
         // return the first type result of the type checker
         let first_type = x
             .type_map
@@ -81,6 +84,13 @@ pub fn type_check(
     .map_err(|(e, _cost_tracker)| e)
 }
 
+/// Run the passes that only produce warnings, appending them to the analysis's `diagnostics`.
+/// These are for tooling: `run_analysis()` doesn't run them, so they cost nothing when
+/// contracts are deployed.
+pub fn lint_contract(contract_analysis: &mut ContractAnalysis) {
+    UnusedTraitChecker::run_pass(contract_analysis);
+}
+
 pub fn run_analysis(
     contract_identifier: &QualifiedContractIdentifier,
     expressions: &mut [SymbolicExpression],
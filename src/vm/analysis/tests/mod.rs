@@ -14,10 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use vm::analysis::errors::CheckErrors;
+use vm::analysis::errors::{CheckError, CheckErrors};
 use vm::analysis::{mem_type_check, AnalysisDatabase};
 use vm::analysis::{type_check, ContractAnalysis};
 use vm::ast::parse;
+use vm::diagnostic::{DiagnosableError, ErrorSummary};
+use vm::types::TupleTypeSignature;
 
 mod costs;
 
@@ -116,8 +118,48 @@ fn test_expected_tuple() {
 fn test_no_such_tuple_field() {
     let snippet = "(get val (tuple (value 100)))";
     let err = mem_type_check(snippet).unwrap_err();
-    assert!(format!("{}", err.diagnostic)
-        .contains("cannot find field 'val' in tuple '(tuple (value int))'"));
+    let display = format!("{}", err.diagnostic);
+    assert!(display
+        .contains("cannot find field 'val' in tuple '(tuple (value int))'; available: value"));
+    // the fields are only listed once
+    assert_eq!(display.matches("available").count(), 1);
+
+    // available fields are listed in sorted order
+    let snippet = "(get val (tuple (c 1) (a 2) (b 3)))";
+    let err = mem_type_check(snippet).unwrap_err();
+    assert!(format!("{}", err.diagnostic).contains("; available: a, b, c"));
+
+    // empty tuple types can't be written in Clarity, but the message should still make sense
+    let empty_tuple: TupleTypeSignature = serde_json::from_str("{\"type_map\":{}}").unwrap();
+    assert_eq!(
+        CheckErrors::NoSuchTupleField("val".into(), empty_tuple).message(),
+        "cannot find field 'val' in tuple '(tuple)'; the tuple has no fields"
+    );
+}
+
+#[test]
+fn test_check_error_summary() {
+    let snippet = "(define-data-var x int 0)\n(var-set x u1)";
+    let err = mem_type_check(snippet).unwrap_err();
+    let summary = ErrorSummary::from(&err);
+    assert_eq!(summary.code, "TypeError");
+    assert_eq!(
+        summary.message,
+        "expecting expression of type 'int', found 'uint'"
+    );
+    assert_eq!(summary.suggestion, None);
+    assert_eq!(summary.spans.len(), 1);
+    assert_eq!(summary.spans[0].0, 2);
+
+    let json = serde_json::to_string(&summary).unwrap();
+    let decoded: ErrorSummary = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, summary);
+
+    // the code names the outer variant, not any error nested inside it
+    let nested = CheckError::new(CheckErrors::BadMatchOptionSyntax(Box::new(
+        CheckErrors::BadSyntaxBinding,
+    )));
+    assert_eq!(ErrorSummary::from(&nested).code, "BadMatchOptionSyntax");
 }
 
 #[test]
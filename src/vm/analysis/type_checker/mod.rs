@@ -133,6 +133,33 @@ impl AnalysisPass for TypeChecker<'_, '_> {
     }
 }
 
+impl TypeChecker<'_, '_> {
+    /// Type check the contract like `run_pass` does, and also report the analysis cost of each
+    /// top-level expression.  An expression's cost is what analyzing the contract up to and
+    /// including that expression costs beyond analyzing it up to the previous one, so the
+    /// costs add up to the cost of analyzing the whole contract.
+    pub fn run_pass_with_expression_costs(
+        contract_analysis: &mut ContractAnalysis,
+        analysis_db: &mut AnalysisDatabase,
+    ) -> CheckResult<Vec<ExecutionCost>> {
+        let cost_track = contract_analysis.take_contract_cost_tracker();
+        let mut command = TypeChecker::new(analysis_db, cost_track);
+        let mut expression_costs = vec![];
+        match command.run_recording_costs(contract_analysis, Some(&mut expression_costs)) {
+            Ok(_) => {
+                let cost_track = command.into_contract_analysis(contract_analysis);
+                contract_analysis.replace_contract_cost_tracker(cost_track);
+                Ok(expression_costs)
+            }
+            Err(e) => {
+                let TypeChecker { cost_track, .. } = command;
+                contract_analysis.replace_contract_cost_tracker(cost_track);
+                Err(e)
+            }
+        }
+    }
+}
+
 pub type TypeResult = CheckResult<TypeSignature>;
 
 impl FunctionType {
@@ -256,7 +283,7 @@ impl FunctionType {
                 ) => {
                     let contract_to_check = db
                         .load_contract(contract)
-                        .ok_or_else(|| CheckErrors::NoSuchContract(contract.name.to_string()))?;
+                        .ok_or_else(|| CheckErrors::NoSuchContract(contract.to_string()))?;
                     let trait_definition = db
                         .get_defined_trait(&trait_id.contract_identifier, &trait_id.name)
                         .unwrap()
@@ -365,9 +392,20 @@ impl<'a, 'b> TypeChecker<'a, 'b> {
     }
 
     pub fn run(&mut self, contract_analysis: &mut ContractAnalysis) -> CheckResult<()> {
+        self.run_recording_costs(contract_analysis, None)
+    }
+
+    /// Type check the contract.  If `expression_costs` is given, push the cost of each
+    /// top-level expression onto it, as described in `run_pass_with_expression_costs`.
+    fn run_recording_costs(
+        &mut self,
+        contract_analysis: &mut ContractAnalysis,
+        mut expression_costs: Option<&mut Vec<ExecutionCost>>,
+    ) -> CheckResult<()> {
         // charge for the eventual storage cost of the analysis --
         //  it is linear in the size of the AST.
         let mut size: u64 = 0;
+        let mut prefix_sizes = vec![];
         for exp in contract_analysis.expressions.iter() {
             depth_traverse(exp, |_x| match size.cost_overflow_add(1) {
                 Ok(new_size) => {
@@ -376,13 +414,18 @@ impl<'a, 'b> TypeChecker<'a, 'b> {
                 }
                 Err(e) => Err(e),
             })?;
+            if expression_costs.is_some() {
+                prefix_sizes.push(size);
+            }
         }
 
         runtime_cost(ClarityCostFunction::AnalysisStorage, self, size)?;
 
         let mut local_context = TypingContext::new();
+        let mut last_total = self.cost_track.get_total();
+        let mut last_storage_cost = ExecutionCost::zero();
 
-        for exp in contract_analysis.expressions.iter() {
+        for (i, exp) in contract_analysis.expressions.iter().enumerate() {
             let mut result_res = self.try_type_check_define(&exp, &mut local_context);
             if let Err(ref mut error) = result_res {
                 if !error.has_expression() {
@@ -394,6 +437,24 @@ impl<'a, 'b> TypeChecker<'a, 'b> {
                 // was _not_ a define statement, so handle like a normal statement.
                 self.type_check(&exp, &local_context)?;
             }
+
+            if let Some(costs) = expression_costs.as_mut() {
+                // the storage cost was charged for the whole contract up front, so charge each
+                // expression for what it adds to the storage cost of the contract so far.
+                let total = self.cost_track.get_total();
+                let mut cost = total.clone();
+                cost.sub(&last_total)?;
+                last_total = total;
+
+                let storage_cost =
+                    self.compute_cost(ClarityCostFunction::AnalysisStorage, &[prefix_sizes[i]])?;
+                let mut storage_cost_delta = storage_cost.clone();
+                storage_cost_delta.sub(&last_storage_cost)?;
+                last_storage_cost = storage_cost;
+
+                cost.add(&storage_cost_delta)?;
+                costs.push(cost);
+            }
         }
         Ok(())
     }
@@ -348,6 +348,27 @@ fn check_special_if(
         .map_err(|_| CheckErrors::IfArmsMustMatch(expr1.clone(), expr2.clone()).into())
 }
 
+// maximum number of function names listed when suggesting what a contract-call? could call
+const MAX_SUGGESTED_FUNCTIONS: usize = 10;
+
+fn callable_functions_suggestion(function_names: &[&ClarityName]) -> Option<String> {
+    if function_names.len() == 0 {
+        return None;
+    }
+    let mut listed: Vec<&str> = function_names
+        .iter()
+        .take(MAX_SUGGESTED_FUNCTIONS)
+        .map(|name| name.as_str())
+        .collect();
+    if function_names.len() > MAX_SUGGESTED_FUNCTIONS {
+        listed.push("...");
+    }
+    Some(format!(
+        "public and read-only functions available: {}",
+        listed.join(", ")
+    ))
+}
+
 fn check_contract_call(
     checker: &mut TypeChecker,
     args: &[SymbolicExpression],
@@ -377,10 +398,26 @@ fn check_contract_call(
                 {
                     Ok(function)
                 } else {
-                    Err(CheckError::new(CheckErrors::NoSuchPublicFunction(
+                    let mut err = CheckError::new(CheckErrors::NoSuchPublicFunction(
                         contract_identifier.to_string(),
                         func_name.to_string(),
-                    )))
+                    ));
+                    if let Some(contract) = checker.db.load_contract(&contract_identifier) {
+                        if contract.get_private_function(func_name).is_some() {
+                            err = CheckError::new(CheckErrors::FunctionIsPrivate(
+                                contract_identifier.to_string(),
+                                func_name.to_string(),
+                            ));
+                        }
+                        err.diagnostic.suggestion = callable_functions_suggestion(
+                            &contract
+                                .public_function_types
+                                .keys()
+                                .chain(contract.read_only_function_types.keys())
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                    Err(err)
                 }
             }?;
 
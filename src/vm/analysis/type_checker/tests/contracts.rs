@@ -598,3 +598,84 @@ fn test_expects() {
         _ => false,
     });
 }
+
+#[test]
+fn test_no_such_public_function_suggestion() {
+    let tokens_contract_id = QualifiedContractIdentifier::local("tokens").unwrap();
+    let caller_contract_id = QualifiedContractIdentifier::local("caller").unwrap();
+
+    let mut tokens_contract = parse(&tokens_contract_id, SIMPLE_TOKENS).unwrap();
+    let mut caller_contract = parse(
+        &caller_contract_id,
+        "(define-public (go) (contract-call? .tokens token-transfr tx-sender u1))",
+    )
+    .unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let err = db
+        .execute(|db| {
+            type_check(&tokens_contract_id, &mut tokens_contract, db, true)?;
+            type_check(&caller_contract_id, &mut caller_contract, db, true)
+        })
+        .unwrap_err();
+
+    match err.err {
+        CheckErrors::NoSuchPublicFunction(ref contract, ref function) => {
+            assert_eq!(contract, &tokens_contract_id.to_string());
+            assert_eq!(function, "token-transfr");
+        }
+        _ => panic!("Unexpected error: {:?}", &err),
+    }
+
+    // the private token-credit! is not callable, so it isn't suggested
+    assert_eq!(
+        err.diagnostic.suggestion,
+        Some(
+            "public and read-only functions available: token-transfer, my-get-token-balance"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_contract_call_private_function() {
+    let tokens_contract_id = QualifiedContractIdentifier::local("tokens").unwrap();
+    let caller_contract_id = QualifiedContractIdentifier::local("caller").unwrap();
+
+    let mut tokens_contract = parse(&tokens_contract_id, SIMPLE_TOKENS).unwrap();
+    let mut caller_contract = parse(
+        &caller_contract_id,
+        "(define-public (go) (contract-call? .tokens token-credit! tx-sender u1))",
+    )
+    .unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let err = db
+        .execute(|db| {
+            type_check(&tokens_contract_id, &mut tokens_contract, db, true)?;
+            type_check(&caller_contract_id, &mut caller_contract, db, true)
+        })
+        .unwrap_err();
+
+    match err.err {
+        CheckErrors::FunctionIsPrivate(ref contract, ref function) => {
+            assert_eq!(contract, &tokens_contract_id.to_string());
+            assert_eq!(function, "token-credit!");
+        }
+        _ => panic!("Unexpected error: {:?}", &err),
+    }
+    assert!(
+        err.diagnostic.message.contains("is private"),
+        "{}",
+        &err.diagnostic.message
+    );
+    assert_eq!(
+        err.diagnostic.suggestion,
+        Some(
+            "public and read-only functions available: token-transfer, my-get-token-balance"
+                .to_string()
+        )
+    );
+}
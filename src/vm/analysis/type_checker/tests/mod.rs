@@ -445,6 +445,56 @@ fn test_trait_reference_unknown() {
     }
 }
 
+#[test]
+fn test_check_error_suggestions() {
+    let suggestion_of = |snippet: &str| {
+        type_check_helper(snippet)
+            .unwrap_err()
+            .diagnostic
+            .suggestion
+    };
+
+    assert_eq!(
+        suggestion_of("(get c (tuple (b 2) (a 1)))"),
+        Some("check the spelling of 'c', or add it where the tuple is constructed".to_string())
+    );
+    assert_eq!(
+        suggestion_of("(not)"),
+        Some("add 1 argument(s): this function takes exactly 1".to_string())
+    );
+    assert_eq!(
+        suggestion_of("(not true false)"),
+        Some("remove 1 argument(s): this function takes exactly 1".to_string())
+    );
+    assert_eq!(
+        suggestion_of("(begin)"),
+        Some("add 1 argument(s): this function takes at least 1".to_string())
+    );
+    assert_eq!(
+        suggestion_of("(len 1)"),
+        Some(
+            "sequence functions operate on values of type list, buff, string-ascii or string-utf8"
+                .to_string()
+        )
+    );
+
+    let contract_id = QualifiedContractIdentifier::local("suggestions").unwrap();
+    let mut contract = parse(&contract_id, "(define-public (foo) 1)").unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let err = db
+        .execute(|db| type_check(&contract_id, &mut contract, db, false))
+        .unwrap_err();
+    assert!(match err.err {
+        CheckErrors::PublicFunctionMustReturnResponse(_) => true,
+        _ => false,
+    });
+    assert_eq!(
+        err.diagnostic.suggestion,
+        Some("wrap the returned value with (ok <value>) or (err <value>)".to_string())
+    );
+}
+
 #[test]
 fn test_unexpected_use_of_field_or_trait_reference() {
     let bad = [(
@@ -20,6 +20,7 @@ use vm::analysis::contract_interface_builder::ContractInterface;
 use vm::analysis::errors::{CheckErrors, CheckResult};
 use vm::analysis::type_checker::contexts::TypeMap;
 use vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker};
+use vm::diagnostic::Diagnostic;
 use vm::types::signatures::FunctionSignature;
 use vm::types::{FunctionType, QualifiedContractIdentifier, TraitIdentifier, TypeSignature};
 use vm::{ClarityName, SymbolicExpression};
@@ -57,6 +58,9 @@ pub struct ContractAnalysis {
     pub type_map: Option<TypeMap>,
     #[serde(skip)]
     pub cost_track: Option<LimitedCostTracker>,
+    /// Non-fatal diagnostics (i.e. warnings) produced by the analysis
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl ContractAnalysis {
@@ -82,6 +86,7 @@ impl ContractAnalysis {
             non_fungible_tokens: BTreeMap::new(),
             cost_track: Some(cost_track),
             is_cost_contract_eligible: false,
+            diagnostics: vec![],
         }
     }
 
@@ -0,0 +1,84 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+
+use vm::analysis::errors::CheckErrors;
+use vm::analysis::types::ContractAnalysis;
+use vm::diagnostic::Diagnostic;
+use vm::functions::define::DefineFunctionsParsed;
+use vm::representations::SymbolicExpressionType::{List, TraitReference};
+use vm::representations::{ClarityName, SymbolicExpression};
+
+/// Warns about traits imported with `use-trait` that are never referenced
+/// by the contract. This pass never fails and does not charge any cost: it
+/// only appends warning-level diagnostics to the contract analysis, so it is
+/// run by `lint_contract()` rather than as part of the contract's analysis.
+pub struct UnusedTraitChecker {}
+
+impl UnusedTraitChecker {
+    pub fn run_pass(contract_analysis: &mut ContractAnalysis) {
+        let mut imported_traits = vec![];
+        let mut referenced_traits = BTreeSet::new();
+
+        for expression in contract_analysis.expressions.iter() {
+            if let Ok(Some(DefineFunctionsParsed::UseTrait { name, .. })) =
+                DefineFunctionsParsed::try_parse(expression)
+            {
+                imported_traits.push((name.clone(), expression));
+            } else {
+                Self::collect_trait_references(expression, &mut referenced_traits);
+            }
+        }
+
+        let mut diagnostics = vec![];
+        for (name, expression) in imported_traits {
+            if referenced_traits.contains(&name) {
+                continue;
+            }
+            let mut diagnostic =
+                Diagnostic::warn(&CheckErrors::UnusedTraitImport(name.to_string()));
+            diagnostic.add_span(
+                expression.span.start_line,
+                expression.span.start_column,
+                expression.span.end_line,
+                expression.span.end_column,
+            );
+            diagnostics.push(diagnostic);
+        }
+        contract_analysis.diagnostics.append(&mut diagnostics);
+    }
+
+    fn collect_trait_references(
+        expression: &SymbolicExpression,
+        referenced_traits: &mut BTreeSet<ClarityName>,
+    ) {
+        match expression.expr {
+            TraitReference(ref name, _) => {
+                referenced_traits.insert(name.clone());
+            }
+            List(ref children) => {
+                for child in children.iter() {
+                    Self::collect_trait_references(child, referenced_traits);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,88 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use vm::analysis::errors::CheckResult;
+use vm::analysis::{lint_contract, type_check};
+use vm::ast::parse;
+use vm::database::MemoryBackingStore;
+use vm::diagnostic::Level;
+use vm::types::QualifiedContractIdentifier;
+
+const CONTRACT_DEFINING_TRAIT: &str = "(define-trait trait-1 (
+        (get-1 (uint) (response uint uint))))";
+
+#[test]
+fn test_unused_trait_import() {
+    let dispatching_contract_src = "(use-trait trait-1 .contract-defining-trait.trait-1)
+        (define-public (get-1 (x uint)) (ok u1))";
+    let used_trait_contract_src = "(use-trait trait-1 .contract-defining-trait.trait-1)
+        (define-public (wrapped-get-1 (contract <trait-1>))
+            (contract-call? contract get-1 u0))";
+
+    let contract_defining_trait_id =
+        QualifiedContractIdentifier::local("contract-defining-trait").unwrap();
+    let dispatching_contract_id =
+        QualifiedContractIdentifier::local("dispatching-contract").unwrap();
+    let used_trait_contract_id = QualifiedContractIdentifier::local("used-trait-contract").unwrap();
+
+    let mut contract_defining_trait =
+        parse(&contract_defining_trait_id, CONTRACT_DEFINING_TRAIT).unwrap();
+    let mut dispatching_contract =
+        parse(&dispatching_contract_id, dispatching_contract_src).unwrap();
+    let mut used_trait_contract = parse(&used_trait_contract_id, used_trait_contract_src).unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let (dispatching_analysis, used_trait_analysis) = db
+        .execute(|db| -> CheckResult<_> {
+            let defining_analysis = type_check(
+                &contract_defining_trait_id,
+                &mut contract_defining_trait,
+                db,
+                true,
+            )?;
+            assert!(defining_analysis.diagnostics.is_empty());
+            let mut dispatching_analysis = type_check(
+                &dispatching_contract_id,
+                &mut dispatching_contract,
+                db,
+                true,
+            )?;
+            let mut used_trait_analysis =
+                type_check(&used_trait_contract_id, &mut used_trait_contract, db, true)?;
+
+            // deploying a contract doesn't lint it
+            assert!(dispatching_analysis.diagnostics.is_empty());
+            lint_contract(&mut dispatching_analysis);
+            lint_contract(&mut used_trait_analysis);
+            Ok((dispatching_analysis, used_trait_analysis))
+        })
+        .unwrap();
+
+    assert_eq!(dispatching_analysis.diagnostics.len(), 1);
+    let diagnostic = &dispatching_analysis.diagnostics[0];
+    assert_eq!(diagnostic.level, Level::Warning);
+    assert!(diagnostic.message.contains("trait-1"));
+    assert!(diagnostic
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("(use-trait trait-1"));
+    assert_eq!(diagnostic.spans.len(), 1);
+    assert_eq!(diagnostic.spans[0].start_line, 1);
+
+    assert!(used_trait_analysis.diagnostics.is_empty());
+}
@@ -17,8 +17,8 @@
 use std::error;
 use std::fmt;
 use vm::costs::{CostErrors, ExecutionCost};
-use vm::diagnostic::{DiagnosableError, Diagnostic};
-use vm::representations::PreSymbolicExpression;
+use vm::diagnostic::{DiagnosableError, Diagnostic, ErrorSummary};
+use vm::representations::{PreSymbolicExpression, Span};
 use vm::types::{TupleTypeSignature, TypeSignature};
 use vm::MAX_CALL_STACK_DEPTH;
 
@@ -95,6 +95,17 @@ impl ParseError {
         self.diagnostic.spans = exprs.iter().map(|e| e.span.clone()).collect();
         self.pre_expressions.replace(exprs.to_vec());
     }
+
+    /// The span of the primary error location, if the parser recorded one.
+    pub fn first_span(&self) -> Option<&Span> {
+        self.diagnostic.spans.first()
+    }
+
+    /// The (line, column) at which the primary error location starts, if known.
+    pub fn line_col(&self) -> Option<(u32, u32)> {
+        self.first_span()
+            .map(|span| (span.start_line, span.start_column))
+    }
 }
 
 impl fmt::Display for ParseError {
@@ -119,6 +130,12 @@ impl error::Error for ParseError {
     }
 }
 
+impl From<&ParseError> for ErrorSummary {
+    fn from(err: &ParseError) -> Self {
+        ErrorSummary::new(err.err.name(), &err.diagnostic)
+    }
+}
+
 impl From<ParseErrors> for ParseError {
     fn from(err: ParseErrors) -> Self {
         ParseError::new(err)
@@ -145,6 +162,51 @@ impl From<CostErrors> for ParseError {
     }
 }
 
+impl ParseErrors {
+    /// The name of this error variant, for tooling that classifies errors.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParseErrors::CostOverflow => "CostOverflow",
+            ParseErrors::CostBalanceExceeded(..) => "CostBalanceExceeded",
+            ParseErrors::MemoryBalanceExceeded(..) => "MemoryBalanceExceeded",
+            ParseErrors::TooManyExpressions => "TooManyExpressions",
+            ParseErrors::ExpressionStackDepthTooDeep => "ExpressionStackDepthTooDeep",
+            ParseErrors::FailedCapturingInput => "FailedCapturingInput",
+            ParseErrors::SeparatorExpected(..) => "SeparatorExpected",
+            ParseErrors::SeparatorExpectedAfterColon(..) => "SeparatorExpectedAfterColon",
+            ParseErrors::ProgramTooLarge => "ProgramTooLarge",
+            ParseErrors::IllegalVariableName(..) => "IllegalVariableName",
+            ParseErrors::IllegalContractName(..) => "IllegalContractName",
+            ParseErrors::UnknownQuotedValue(..) => "UnknownQuotedValue",
+            ParseErrors::FailedParsingIntValue(..) => "FailedParsingIntValue",
+            ParseErrors::FailedParsingBuffer(..) => "FailedParsingBuffer",
+            ParseErrors::FailedParsingHexValue(..) => "FailedParsingHexValue",
+            ParseErrors::FailedParsingPrincipal(..) => "FailedParsingPrincipal",
+            ParseErrors::FailedParsingField(..) => "FailedParsingField",
+            ParseErrors::FailedParsingRemainder(..) => "FailedParsingRemainder",
+            ParseErrors::ClosingParenthesisUnexpected => "ClosingParenthesisUnexpected",
+            ParseErrors::ClosingParenthesisExpected => "ClosingParenthesisExpected",
+            ParseErrors::ClosingTupleLiteralUnexpected => "ClosingTupleLiteralUnexpected",
+            ParseErrors::ClosingTupleLiteralExpected => "ClosingTupleLiteralExpected",
+            ParseErrors::CircularReference(..) => "CircularReference",
+            ParseErrors::TupleColonExpected(..) => "TupleColonExpected",
+            ParseErrors::TupleCommaExpected(..) => "TupleCommaExpected",
+            ParseErrors::TupleItemExpected(..) => "TupleItemExpected",
+            ParseErrors::NameAlreadyUsed(..) => "NameAlreadyUsed",
+            ParseErrors::TraitReferenceNotAllowed => "TraitReferenceNotAllowed",
+            ParseErrors::ImportTraitBadSignature => "ImportTraitBadSignature",
+            ParseErrors::DefineTraitBadSignature => "DefineTraitBadSignature",
+            ParseErrors::ImplTraitBadSignature => "ImplTraitBadSignature",
+            ParseErrors::TraitReferenceUnknown(..) => "TraitReferenceUnknown",
+            ParseErrors::CommaSeparatorUnexpected => "CommaSeparatorUnexpected",
+            ParseErrors::ColonSeparatorUnexpected => "ColonSeparatorUnexpected",
+            ParseErrors::InvalidCharactersDetected => "InvalidCharactersDetected",
+            ParseErrors::InvalidEscaping => "InvalidEscaping",
+            ParseErrors::CostComputationFailed(..) => "CostComputationFailed",
+        }
+    }
+}
+
 impl DiagnosableError for ParseErrors {
     fn message(&self) -> String {
         match &self {
@@ -126,12 +126,12 @@ lazy_static! {
         format!(r#"([[:word:]]|[-!?+<>=/*]){{1,{}}}"#, MAX_STRING_LEN);
 }
 
-pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
+fn make_lex_matchers() -> Vec<LexMatcher> {
     // Aaron: I'd like these to be static, but that'd require using
     //    lazy_static (or just hand implementing that), and I'm not convinced
     //    it's worth either (1) an extern macro, or (2) the complexity of hand implementing.
 
-    let lex_matchers: &[LexMatcher] = &[
+    vec![
         LexMatcher::new(
             r##"u"(?P<value>((\\")|([[ -~]&&[^"]]))*)""##,
             TokenType::StringUTF8Literal,
@@ -186,38 +186,87 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
             &format!("(?P<value>{})", *CLARITY_NAME_REGEX),
             TokenType::Variable,
         ),
-    ];
+    ]
+}
+
+/// Where the lexer has got to in its input.  Lexing stops at the first error, but it can be
+/// resumed from another position (see `parse_collect()`).
+struct LexCursor {
+    munch_index: usize,
+    context: LexContext,
+    line_indices: Vec<usize>,
+    next_line_break: Option<usize>,
+    current_line: u32,
+    column_pos: u32,
+}
 
-    let mut context = LexContext::ExpectNothing;
+impl LexCursor {
+    fn new(input: &str) -> LexCursor {
+        let mut line_indices = get_lines_at(input);
+        let next_line_break = line_indices.pop();
+        LexCursor {
+            munch_index: 0,
+            context: LexContext::ExpectNothing,
+            line_indices,
+            next_line_break,
+            current_line: 1,
+            column_pos: 1,
+        }
+    }
 
-    let mut line_indices = get_lines_at(input);
-    let mut next_line_break = line_indices.pop();
-    let mut current_line: u32 = 1;
+    /// Move to the start of the line beginning at byte `index`, which must come after the
+    /// current position.
+    fn restart_line_at(&mut self, index: usize) {
+        while let Some(next_line_ix) = self.next_line_break {
+            if next_line_ix >= index {
+                break;
+            }
+            self.next_line_break = self.line_indices.pop();
+            self.current_line = self.current_line.saturating_add(1);
+        }
+        self.munch_index = index;
+        self.context = LexContext::ExpectNothing;
+        self.column_pos = 1;
+    }
+}
 
+pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
+    let lex_matchers = make_lex_matchers();
+    let mut cursor = LexCursor::new(input);
     let mut result = Vec::new();
-    let mut munch_index = 0;
-    let mut column_pos: u32 = 1;
+    lex_from(input, &lex_matchers, &mut cursor, &mut result)?;
+    Ok(result)
+}
+
+/// Lex `input` from `cursor` to the end, appending the tokens to `result`.
+fn lex_from(
+    input: &str,
+    lex_matchers: &[LexMatcher],
+    cursor: &mut LexCursor,
+    result: &mut Vec<(LexItem, u32, u32)>,
+) -> ParseResult<()> {
     let mut did_match = true;
-    while did_match && munch_index < input.len() {
-        if let Some(next_line_ix) = next_line_break {
-            if munch_index > next_line_ix {
-                next_line_break = line_indices.pop();
-                column_pos = 1;
-                current_line = current_line
+    while did_match && cursor.munch_index < input.len() {
+        if let Some(next_line_ix) = cursor.next_line_break {
+            if cursor.munch_index > next_line_ix {
+                cursor.next_line_break = cursor.line_indices.pop();
+                cursor.column_pos = 1;
+                cursor.current_line = cursor
+                    .current_line
                     .checked_add(1)
                     .ok_or(ParseError::new(ParseErrors::ProgramTooLarge))?;
             }
         }
 
         did_match = false;
-        let current_slice = &input[munch_index..];
+        let current_slice = &input[cursor.munch_index..];
         for matcher in lex_matchers.iter() {
             if let Some(captures) = matcher.matcher.captures(current_slice) {
                 let whole_match = captures.get(0).unwrap();
                 assert_eq!(whole_match.start(), 0);
-                munch_index += whole_match.end();
+                cursor.munch_index += whole_match.end();
 
-                match context {
+                match cursor.context {
                     LexContext::ExpectNothing => Ok(()),
                     LexContext::ExpectClosing => {
                         // expect the next lexed item to be something that typically
@@ -250,30 +299,30 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                 }?;
 
                 // default to expect a closing
-                context = LexContext::ExpectClosing;
+                cursor.context = LexContext::ExpectClosing;
 
                 let token = match matcher.handler {
                     TokenType::LParens => {
-                        context = LexContext::ExpectNothing;
+                        cursor.context = LexContext::ExpectNothing;
                         Ok(LexItem::LeftParen)
                     }
                     TokenType::RParens => Ok(LexItem::RightParen),
                     TokenType::Whitespace => {
-                        context = LexContext::ExpectNothing;
+                        cursor.context = LexContext::ExpectNothing;
                         Ok(LexItem::Whitespace)
                     }
                     TokenType::Comma => {
-                        context = LexContext::ExpectNothing;
+                        cursor.context = LexContext::ExpectNothing;
                         Ok(LexItem::CommaSeparator)
                     }
                     TokenType::Colon => {
                         // colon should not be followed directly by an item,
                         //  e.g., {a:b} should not be legal
-                        context = LexContext::ExpectClosingColon;
+                        cursor.context = LexContext::ExpectClosingColon;
                         Ok(LexItem::ColonSeparator)
                     }
                     TokenType::LCurly => {
-                        context = LexContext::ExpectNothing;
+                        cursor.context = LexContext::ExpectNothing;
                         Ok(LexItem::LeftCurly)
                     }
                     TokenType::RCurly => Ok(LexItem::RightCurly),
@@ -410,19 +459,19 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                     }
                 }?;
 
-                result.push((token, current_line, column_pos));
-                column_pos += whole_match.end() as u32;
+                result.push((token, cursor.current_line, cursor.column_pos));
+                cursor.column_pos += whole_match.end() as u32;
                 did_match = true;
                 break;
             }
         }
     }
 
-    if munch_index == input.len() {
-        Ok(result)
+    if cursor.munch_index == input.len() {
+        Ok(())
     } else {
         Err(ParseError::new(ParseErrors::FailedParsingRemainder(
-            input[munch_index..].to_string(),
+            input[cursor.munch_index..].to_string(),
         )))
     }
 }
@@ -693,10 +742,141 @@ pub fn parse(input: &str) -> ParseResult<Vec<PreSymbolicExpression>> {
     parse_lexed(lexed)
 }
 
+/// How a token changes the nesting depth of lists and tuples.
+fn token_depth_change(token: &LexItem) -> i32 {
+    match token {
+        LexItem::LeftParen | LexItem::LeftCurly => 1,
+        LexItem::RightParen | LexItem::RightCurly => -1,
+        _ => 0,
+    }
+}
+
+/// How many of `tokens` belong to complete top-level forms, i.e. where the last top-level form
+/// that is still open begins.
+fn complete_forms_len(tokens: &[(LexItem, u32, u32)]) -> usize {
+    let mut depth = 0;
+    let mut complete = 0;
+    for (i, (token, ..)) in tokens.iter().enumerate() {
+        depth += token_depth_change(token);
+        if depth <= 0 {
+            depth = 0;
+            complete = i + 1;
+        }
+    }
+    complete
+}
+
+/// Parse each top-level form in `tokens` on its own, so that an error only spoils the form it
+/// occurs in.  A form that is missing a closing parenthesis swallows every form after it, so if
+/// `recover` is set, an unclosed form is split again wherever an opening parenthesis starts a
+/// line, and each piece is parsed on its own.
+fn parse_forms(
+    tokens: Vec<(LexItem, u32, u32)>,
+    recover: bool,
+    expressions: &mut Vec<PreSymbolicExpression>,
+    errors: &mut Vec<ParseError>,
+) {
+    let mut form = vec![];
+    let mut depth = 0;
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        depth += token_depth_change(&token.0);
+        form.push(token);
+        if depth > 0 && tokens.peek().is_some() {
+            continue;
+        }
+        let unclosed = depth > 0;
+        depth = 0;
+
+        let mut form_tokens = ::std::mem::replace(&mut form, vec![]);
+        if recover && unclosed {
+            let restarts: Vec<usize> = form_tokens
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(_, (token, _, column))| *column == 1 && token_depth_change(token) > 0)
+                .map(|(i, _)| i)
+                .collect();
+            if !restarts.is_empty() {
+                let mut pieces = vec![];
+                for restart in restarts.into_iter().rev() {
+                    pieces.push(form_tokens.split_off(restart));
+                }
+                pieces.push(form_tokens);
+                for piece in pieces.into_iter().rev() {
+                    parse_forms(piece, false, expressions, errors);
+                }
+                continue;
+            }
+        }
+
+        match parse_lexed(form_tokens) {
+            Ok(mut form_expressions) => expressions.append(&mut form_expressions),
+            Err(e) => errors.push(e),
+        }
+    }
+}
+
+/// Parse `input`, collecting every parse error instead of stopping at the first one.  Each
+/// top-level form is parsed on its own, so an error only spoils the form it occurs in, and
+/// parsing resumes at the next form.  Spans are reported relative to the whole of `input`.
+/// Returns the parsed expressions if there were no errors -- which are then the same as
+/// `parse()` returns -- and all the errors otherwise.
+pub fn parse_collect(
+    input: &str,
+) -> ::std::result::Result<Vec<PreSymbolicExpression>, Vec<ParseError>> {
+    let lex_matchers = make_lex_matchers();
+    let mut cursor = LexCursor::new(input);
+    let mut expressions = vec![];
+    let mut errors = vec![];
+    loop {
+        let mut tokens = vec![];
+        let lexed = lex_from(input, &lex_matchers, &mut cursor, &mut tokens);
+        let lex_error = match lexed {
+            Ok(()) => {
+                parse_forms(tokens, true, &mut expressions, &mut errors);
+                break;
+            }
+            Err(e) => e,
+        };
+
+        // the form that failed to lex is dropped, and lexing resumes at the next line that
+        // starts with an opening parenthesis
+        let complete = complete_forms_len(&tokens);
+        tokens.truncate(complete);
+        parse_forms(tokens, true, &mut expressions, &mut errors);
+
+        let failed_at = cmp::min(cursor.munch_index, input.len());
+        let restart = input[failed_at..].find("\n(").map(|ix| failed_at + ix + 1);
+        // the unlexed remainder only runs up to where lexing resumes
+        let lex_error = match (&lex_error.err, restart) {
+            (ParseErrors::FailedParsingRemainder(_), Some(restart)) => {
+                ParseError::new(ParseErrors::FailedParsingRemainder(
+                    input[failed_at..restart].trim_end().to_string(),
+                ))
+            }
+            _ => lex_error,
+        };
+        errors.push(lex_error);
+
+        match restart {
+            Some(restart) => cursor.restart_line_at(restart),
+            None => break,
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(expressions)
+    } else {
+        Err(errors)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use vm::ast;
     use vm::ast::errors::{ParseError, ParseErrors};
+    use vm::diagnostic::ErrorSummary;
     use vm::representations::{PreSymbolicExpression, PreSymbolicExpressionType};
     use vm::types::TraitIdentifier;
     use vm::types::{CharType, PrincipalData, QualifiedContractIdentifier, SequenceData, Value};
@@ -1229,4 +1409,91 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn test_parse_error_location() {
+        // unclosed list: reported at the opening parenthesis
+        let unclosed = "(define-constant a 1)\n  (define-constant b (+ 1 2)";
+        let err = ast::parser::parse(unclosed).unwrap_err();
+        assert_eq!(err.err, ParseErrors::ClosingParenthesisExpected);
+        assert_eq!(err.line_col(), Some((2, 3)));
+        assert_eq!(err.first_span(), err.diagnostic.spans.first());
+
+        // unclosed tuple literal: reported at the opening brace
+        let wrong_tuple_close = "(list 1 2)\n(list\n    {id 1337))";
+        let err = ast::parser::parse(wrong_tuple_close).unwrap_err();
+        assert_eq!(err.err, ParseErrors::ClosingTupleLiteralExpected);
+        assert_eq!(err.line_col(), Some((3, 5)));
+        let span = err.first_span().unwrap();
+        assert_eq!((span.end_line, span.end_column), (3, 13));
+
+        // no location recorded
+        let err = ParseError::new(ParseErrors::FailedCapturingInput);
+        assert_eq!(err.first_span(), None);
+        assert_eq!(err.line_col(), None);
+    }
+
+    #[test]
+    fn test_parse_collect() {
+        let contract = "(define-constant a (+ 1 2)))
+(define-constant b \"a (string) ;; with parens\") ;; (comment
+(define-constant c (list 1 2 #))
+(define-constant d {x: 1, y: 2})
+(define-constant e (+ 1
+(define-constant f 1)";
+
+        let errors = ast::parser::parse_collect(contract).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].err, ParseErrors::ClosingParenthesisUnexpected);
+        assert_eq!(
+            errors[1].err,
+            ParseErrors::FailedParsingRemainder("#))".to_string())
+        );
+        assert_eq!(errors[2].err, ParseErrors::ClosingParenthesisExpected);
+        assert_eq!(errors[2].line_col(), Some((5, 20)));
+
+        // without errors, the result is the same as parse()
+        let contract = "(define-constant b \"a (string) ;; with parens\") ;; (comment
+(define-constant d {x: 1, y: 2})
+   (define-constant f
+      1)";
+        assert_eq!(
+            ast::parser::parse_collect(contract).unwrap(),
+            ast::parser::parse(contract).unwrap()
+        );
+
+        // nested forms may start a line, and blank lines don't change the spans
+        let contract = "(define-public (foo)
+(ok 1))
+
+
+(define-constant a
+(list 1
+    2))";
+        let expressions = ast::parser::parse_collect(contract).unwrap();
+        assert_eq!(expressions.len(), 2);
+        assert_eq!(expressions, ast::parser::parse(contract).unwrap());
+
+        // whatever parse() rejects is rejected too
+        let contract = "(define-constant a 1)(define-constant b 2)";
+        assert!(ast::parser::parse(contract).is_err());
+        assert_eq!(ast::parser::parse_collect(contract).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_error_summary() {
+        let err = ast::parser::parse("(list 1 2)\n(list\n    {id 1337))").unwrap_err();
+        let summary = ErrorSummary::from(&err);
+        assert_eq!(summary.code, "ClosingTupleLiteralExpected");
+        assert_eq!(summary.message, err.diagnostic.message);
+        assert_eq!(summary.suggestion, None);
+        assert_eq!(summary.spans, vec![(3, 5, 3, 13)]);
+
+        // variants with data are named without it
+        let summary = ErrorSummary::from(&ParseError::new(ParseErrors::SeparatorExpected(
+            "foo".to_string(),
+        )));
+        assert_eq!(summary.code, "SeparatorExpected");
+        assert!(summary.spans.is_empty());
+    }
 }
@@ -237,6 +237,10 @@ impl DefinedFunction {
     pub fn get_identifier(&self) -> FunctionIdentifier {
         self.identifier.clone()
     }
+
+    pub fn get_name(&self) -> &ClarityName {
+        &self.name
+    }
 }
 
 impl CallableType {
@@ -19,7 +19,7 @@ use vm::analysis::AnalysisDatabase;
 use vm::analysis::{errors::CheckError, errors::CheckErrors, ContractAnalysis};
 use vm::ast;
 use vm::ast::{errors::ParseError, errors::ParseErrors, ContractAST};
-use vm::contexts::{AssetMap, Environment, OwnedEnvironment};
+use vm::contexts::{AssetMap, CostErrorLocation, Environment, OwnedEnvironment};
 use vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker};
 use vm::database::{
     marf::WritableMarfStore, BurnStateDB, ClarityDatabase, HeadersDB, MarfedKV, RollbackWrapper,
@@ -31,6 +31,7 @@ use vm::types::{
     AssetIdentifier, PrincipalData, QualifiedContractIdentifier, TypeSignature, Value,
 };
 
+use burnchains::Txid;
 use chainstate::burn::BlockHeaderHash;
 use chainstate::stacks::events::StacksTransactionEvent;
 use chainstate::stacks::index::marf::MARF;
@@ -105,7 +106,10 @@ pub enum Error {
     Parse(ParseError),
     Interpreter(InterpreterError),
     BadTransaction(String),
-    CostError(ExecutionCost, ExecutionCost),
+    /// Spent cost, cost budget, and (if known) the user-defined function that exceeded the budget
+    CostError(ExecutionCost, ExecutionCost, Option<CostErrorLocation>),
+    /// Memory used and memory limit
+    MemoryBalanceExceeded(u64, u64),
     AbortedByCallback(Option<Value>, AssetMap, Vec<StacksTransactionEvent>),
 }
 
@@ -113,12 +117,10 @@ impl From<CheckError> for Error {
     fn from(e: CheckError) -> Self {
         match e.err {
             CheckErrors::CostOverflow => {
-                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
-            }
-            CheckErrors::CostBalanceExceeded(a, b) => Error::CostError(a, b),
-            CheckErrors::MemoryBalanceExceeded(_a, _b) => {
-                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
+                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value(), None)
             }
+            CheckErrors::CostBalanceExceeded(a, b) => Error::CostError(a, b, None),
+            CheckErrors::MemoryBalanceExceeded(a, b) => Error::MemoryBalanceExceeded(a, b),
             _ => Error::Analysis(e),
         }
     }
@@ -128,10 +130,13 @@ impl From<InterpreterError> for Error {
     fn from(e: InterpreterError) -> Self {
         match &e {
             InterpreterError::Unchecked(CheckErrors::CostBalanceExceeded(a, b)) => {
-                Error::CostError(a.clone(), b.clone())
+                Error::CostError(a.clone(), b.clone(), None)
             }
             InterpreterError::Unchecked(CheckErrors::CostOverflow) => {
-                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
+                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value(), None)
+            }
+            InterpreterError::Unchecked(CheckErrors::MemoryBalanceExceeded(a, b)) => {
+                Error::MemoryBalanceExceeded(*a, *b)
             }
             _ => Error::Interpreter(e),
         }
@@ -142,12 +147,10 @@ impl From<ParseError> for Error {
     fn from(e: ParseError) -> Self {
         match e.err {
             ParseErrors::CostOverflow => {
-                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
-            }
-            ParseErrors::CostBalanceExceeded(a, b) => Error::CostError(a, b),
-            ParseErrors::MemoryBalanceExceeded(_a, _b) => {
-                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value())
+                Error::CostError(ExecutionCost::max_value(), ExecutionCost::max_value(), None)
             }
+            ParseErrors::CostBalanceExceeded(a, b) => Error::CostError(a, b, None),
+            ParseErrors::MemoryBalanceExceeded(a, b) => Error::MemoryBalanceExceeded(a, b),
             _ => Error::Parse(e),
         }
     }
@@ -157,7 +160,10 @@ impl From<ChainstateError> for Error {
     fn from(e: ChainstateError) -> Self {
         match e {
             ChainstateError::InvalidStacksTransaction(msg, _) => Error::BadTransaction(msg),
-            ChainstateError::CostOverflowError(_, after, budget) => Error::CostError(after, budget),
+            ChainstateError::BadNonce(e) => Error::BadTransaction(e.to_string()),
+            ChainstateError::CostOverflowError(_, after, budget) => {
+                Error::CostError(after, budget, None)
+            }
             ChainstateError::ClarityError(x) => x,
             x => Error::BadTransaction(format!("{:?}", &x)),
         }
@@ -167,9 +173,18 @@ impl From<ChainstateError> for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::CostError(ref a, ref b) => {
-                write!(f, "Cost Error: {} cost exceeded budget of {} cost", a, b)
+            Error::CostError(ref a, ref b, ref location) => {
+                write!(f, "Cost Error: {} cost exceeded budget of {} cost", a, b)?;
+                if let Some(location) = location {
+                    write!(f, " in {}", location)?;
+                }
+                Ok(())
             }
+            Error::MemoryBalanceExceeded(ref used, ref limit) => write!(
+                f,
+                "Memory Error: {} bytes used exceeded limit of {} bytes",
+                used, limit
+            ),
             Error::Analysis(ref e) => fmt::Display::fmt(e, f),
             Error::Parse(ref e) => fmt::Display::fmt(e, f),
             Error::AbortedByCallback(..) => write!(f, "Post condition aborted transaction"),
@@ -179,10 +194,158 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// If this transaction was aborted by a post-condition callback, get the value it would
+    /// have returned, the assets it moved, and the events it emitted.
+    pub fn abort_details(&self) -> Option<(&Option<Value>, &AssetMap, &[StacksTransactionEvent])> {
+        match self {
+            Error::AbortedByCallback(ref value, ref assets, ref events) => {
+                Some((value, assets, events))
+            }
+            _ => None,
+        }
+    }
+
+    /// Did this error happen because the transaction ran out of its cost or memory budget?
+    /// This is true for `CostError` and `MemoryBalanceExceeded`, and for analysis, parse, and
+    /// interpreter errors that wrap a budget error without having been converted into one.
+    /// A failure to *compute* a cost is not a cost error.
+    pub fn is_cost_error(&self) -> bool {
+        match self {
+            Error::CostError(..) | Error::MemoryBalanceExceeded(..) => true,
+            Error::Analysis(ref e) => match e.err {
+                CheckErrors::CostOverflow
+                | CheckErrors::CostBalanceExceeded(..)
+                | CheckErrors::MemoryBalanceExceeded(..) => true,
+                _ => false,
+            },
+            Error::Parse(ref e) => match e.err {
+                ParseErrors::CostOverflow
+                | ParseErrors::CostBalanceExceeded(..)
+                | ParseErrors::MemoryBalanceExceeded(..) => true,
+                _ => false,
+            },
+            Error::Interpreter(InterpreterError::Unchecked(ref e)) => match e {
+                CheckErrors::CostOverflow
+                | CheckErrors::CostBalanceExceeded(..)
+                | CheckErrors::MemoryBalanceExceeded(..) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Error::Analysis(..) => "Analysis",
+            Error::Parse(..) => "Parse",
+            Error::Interpreter(..) => "Interpreter",
+            Error::BadTransaction(..) => "BadTransaction",
+            Error::CostError(..) => "CostError",
+            Error::MemoryBalanceExceeded(..) => "MemoryBalanceExceeded",
+            Error::AbortedByCallback(..) => "AbortedByCallback",
+        }
+    }
+
+    pub fn into_json(&self) -> serde_json::Value {
+        let reason_data = match self {
+            Error::Analysis(ref e) => json!({
+                "message": e.diagnostic.message,
+                "spans": e.diagnostic.spans,
+                "suggestion": e.diagnostic.suggestion,
+            }),
+            Error::Parse(ref e) => json!({
+                "message": e.diagnostic.message,
+                "spans": e.diagnostic.spans,
+                "suggestion": e.diagnostic.suggestion,
+            }),
+            Error::Interpreter(ref e) => {
+                let (kind, message) = match e {
+                    InterpreterError::Unchecked(ref e) => ("Unchecked", format!("{:?}", e)),
+                    InterpreterError::Interpreter(ref e) => ("Interpreter", format!("{:?}", e)),
+                    InterpreterError::Runtime(ref e, _) => ("Runtime", format!("{:?}", e)),
+                    InterpreterError::ShortReturn(ref e) => ("ShortReturn", format!("{:?}", e)),
+                };
+                json!({
+                    "kind": kind,
+                    "message": message,
+                })
+            }
+            Error::BadTransaction(ref s) => json!({ "message": s }),
+            Error::CostError(ref cost, ref budget, ref location) => json!({
+                "cost": cost,
+                "budget": budget,
+                "contract_identifier": location.as_ref().map(|l| l.contract_identifier.to_string()),
+                "function_name": location.as_ref().map(|l| l.function_name.to_string()),
+            }),
+            Error::MemoryBalanceExceeded(ref used, ref limit) => json!({
+                "used": used,
+                "limit": limit,
+            }),
+            Error::AbortedByCallback(ref value, ref assets, ref events) => {
+                let events_json: Vec<_> = events
+                    .iter()
+                    .enumerate()
+                    .map(|(i, event)| {
+                        // the transaction was never mined, so there's no meaningful txid
+                        let mut event_json = event.json_serialize(i, &Txid([0; 32]), false);
+                        if let Some(event_obj) = event_json.as_object_mut() {
+                            event_obj.remove("txid");
+                        }
+                        event_json
+                    })
+                    .collect();
+                json!({
+                    "value": value.as_ref().map(|v| v.to_string()),
+                    "assets": assets.json_serialize(),
+                    "asset_summary": assets.summary(),
+                    "events": events_json,
+                })
+            }
+        };
+        json!({
+            "error": "clarity error",
+            "reason": self.name(),
+            "reason_data": reason_data
+        })
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Error {
+    /// (make Clarity errors comparable for testing purposes)
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::Analysis(e1), Error::Analysis(e2)) => e1 == e2,
+            (Error::Parse(e1), Error::Parse(e2)) => e1 == e2,
+            (Error::Interpreter(e1), Error::Interpreter(e2)) => e1 == e2,
+            (Error::BadTransaction(s1), Error::BadTransaction(s2)) => s1 == s2,
+            (Error::CostError(cost1, budget1, loc1), Error::CostError(cost2, budget2, loc2)) => {
+                cost1 == cost2 && budget1 == budget2 && loc1 == loc2
+            }
+            (
+                Error::MemoryBalanceExceeded(used1, limit1),
+                Error::MemoryBalanceExceeded(used2, limit2),
+            ) => used1 == used2 && limit1 == limit2,
+            (
+                Error::AbortedByCallback(value1, assets1, events1),
+                Error::AbortedByCallback(value2, assets2, events2),
+            ) => {
+                // asset maps are only comparable once tabulated
+                value1 == value2
+                    && assets1.clone().to_table() == assets2.clone().to_table()
+                    && events1 == events2
+            }
+            _ => false,
+        }
+    }
+}
+
 impl error::Error for Error {
     fn cause(&self) -> Option<&dyn error::Error> {
         match *self {
-            Error::CostError(ref _a, ref _b) => None,
+            Error::CostError(..) => None,
+            Error::MemoryBalanceExceeded(..) => None,
             Error::AbortedByCallback(..) => None,
             Error::Analysis(ref e) => Some(e),
             Error::Parse(ref e) => Some(e),
@@ -787,7 +950,12 @@ impl<'a, 'b> ClarityTransactionConnection<'a, 'b> {
                 //   so we can abort on call_back's boolean retun
                 db.begin();
                 let mut vm_env = OwnedEnvironment::new_cost_limited(self.mainnet, db, cost_track);
-                let result = to_do(&mut vm_env);
+                let result = to_do(&mut vm_env).map_err(|e| match e {
+                    Error::CostError(cost, budget, None) => {
+                        Error::CostError(cost, budget, vm_env.cost_error_location().cloned())
+                    }
+                    e => e,
+                });
                 let (mut db, cost_track) = vm_env
                     .destruct()
                     .expect("Failed to recover database reference after executing transaction");
@@ -1002,6 +1170,223 @@ mod tests {
     };
     use vm::types::{StandardPrincipalData, Value};
 
+    #[test]
+    pub fn error_into_json() {
+        let div_by_zero = Error::from(::vm::execute("(/ 1 0)").unwrap_err());
+        let json = div_by_zero.into_json();
+        assert_eq!(json["error"], "clarity error");
+        assert_eq!(json["reason"], "Interpreter");
+        assert_eq!(json["reason_data"]["kind"], "Runtime");
+        assert_eq!(json["reason_data"]["message"], "DivisionByZero");
+
+        let cost = ExecutionCost {
+            write_length: 1,
+            write_count: 2,
+            read_length: 3,
+            read_count: 4,
+            runtime: 500,
+        };
+        let budget = ExecutionCost {
+            write_length: 10,
+            write_count: 20,
+            read_length: 30,
+            read_count: 40,
+            runtime: 50,
+        };
+        let cost_err = Error::CostError(cost.clone(), budget.clone(), None);
+        let json = cost_err.into_json();
+        assert_eq!(json["error"], "clarity error");
+        assert_eq!(json["reason"], "CostError");
+        assert_eq!(json["reason_data"]["cost"]["runtime"], 500);
+        assert_eq!(json["reason_data"]["budget"]["runtime"], 50);
+        let decoded: ExecutionCost =
+            serde_json::from_value(json["reason_data"]["budget"].clone()).unwrap();
+        assert_eq!(decoded, budget);
+
+        assert_ne!(div_by_zero.into_json(), cost_err.into_json());
+    }
+
+    #[test]
+    pub fn memory_balance_exceeded_preserves_usage() {
+        let err = Error::from(CheckError::new(CheckErrors::MemoryBalanceExceeded(
+            200, 100,
+        )));
+        assert_eq!(err, Error::MemoryBalanceExceeded(200, 100));
+        assert_eq!(
+            err.to_string(),
+            "Memory Error: 200 bytes used exceeded limit of 100 bytes"
+        );
+        assert_eq!(err.into_json()["reason_data"]["used"], 200);
+        assert_eq!(err.into_json()["reason_data"]["limit"], 100);
+
+        let err = Error::from(InterpreterError::Unchecked(
+            CheckErrors::MemoryBalanceExceeded(300, 150),
+        ));
+        assert_eq!(err, Error::MemoryBalanceExceeded(300, 150));
+
+        let err = Error::from(ParseError::new(ParseErrors::MemoryBalanceExceeded(
+            400, 250,
+        )));
+        assert_eq!(err, Error::MemoryBalanceExceeded(400, 250));
+    }
+
+    #[test]
+    pub fn is_cost_error() {
+        let cost = ExecutionCost {
+            write_length: 1,
+            write_count: 2,
+            read_length: 3,
+            read_count: 4,
+            runtime: 500,
+        };
+        let budget = ExecutionCost {
+            runtime: 50,
+            ..cost.clone()
+        };
+
+        assert!(Error::CostError(cost.clone(), budget.clone(), None).is_cost_error());
+        assert!(Error::MemoryBalanceExceeded(200, 100).is_cost_error());
+
+        // budget errors that were wrapped directly, instead of going through From
+        assert!(
+            Error::Analysis(CheckError::new(CheckErrors::CostBalanceExceeded(
+                cost.clone(),
+                budget.clone()
+            )))
+            .is_cost_error()
+        );
+        assert!(Error::Interpreter(InterpreterError::Unchecked(
+            CheckErrors::CostBalanceExceeded(cost.clone(), budget.clone())
+        ))
+        .is_cost_error());
+        assert!(Error::Parse(ParseError::new(ParseErrors::CostOverflow)).is_cost_error());
+
+        // malformed transactions are not cost errors
+        assert!(!Error::Analysis(CheckError::new(CheckErrors::TypeError(
+            TypeSignature::IntType,
+            TypeSignature::UIntType
+        )))
+        .is_cost_error());
+        assert!(
+            !Error::Analysis(CheckError::new(CheckErrors::CostComputationFailed(
+                "no cost contract".into()
+            )))
+            .is_cost_error()
+        );
+        assert!(!Error::BadTransaction("cost".into()).is_cost_error());
+    }
+
+    #[test]
+    pub fn error_eq() {
+        let cost = ExecutionCost {
+            write_length: 1,
+            write_count: 2,
+            read_length: 3,
+            read_count: 4,
+            runtime: 500,
+        };
+        let budget = ExecutionCost {
+            runtime: 50,
+            ..cost.clone()
+        };
+
+        assert_eq!(
+            Error::CostError(cost.clone(), budget.clone(), None),
+            Error::CostError(cost.clone(), budget.clone(), None)
+        );
+        assert_ne!(
+            Error::CostError(cost.clone(), budget.clone(), None),
+            Error::CostError(budget.clone(), cost.clone(), None)
+        );
+        assert_ne!(
+            Error::CostError(cost.clone(), budget.clone(), None),
+            Error::CostError(
+                cost.clone(),
+                budget.clone(),
+                Some(CostErrorLocation {
+                    contract_identifier: QualifiedContractIdentifier::local("foo").unwrap(),
+                    function_name: "bar".into(),
+                })
+            )
+        );
+        assert_ne!(
+            Error::CostError(cost.clone(), budget.clone(), None),
+            Error::BadTransaction("cost".into())
+        );
+
+        // asset maps compare by contents
+        let sender = PrincipalData::from(StandardPrincipalData(1, [1; 20]));
+        let other = PrincipalData::from(StandardPrincipalData(1, [2; 20]));
+        let mut assets1 = AssetMap::new();
+        assets1.add_stx_transfer(&sender, 1).unwrap();
+        assets1.add_stx_transfer(&other, 2).unwrap();
+        let mut assets2 = AssetMap::new();
+        assets2.add_stx_transfer(&other, 2).unwrap();
+        assets2.add_stx_transfer(&sender, 1).unwrap();
+        assert_eq!(
+            Error::AbortedByCallback(None, assets1.clone(), vec![]),
+            Error::AbortedByCallback(None, assets2, vec![])
+        );
+        assert_ne!(
+            Error::AbortedByCallback(None, assets1, vec![]),
+            Error::AbortedByCallback(None, AssetMap::new(), vec![])
+        );
+    }
+
+    #[test]
+    pub fn aborted_by_callback_details() {
+        let sender = PrincipalData::from(StandardPrincipalData(1, [1; 20]));
+        let recipient = PrincipalData::from(StandardPrincipalData(1, [2; 20]));
+        let token = AssetIdentifier {
+            contract_identifier: QualifiedContractIdentifier::local("tokens").unwrap(),
+            asset_name: "gold".into(),
+        };
+
+        let mut assets = AssetMap::new();
+        assets.add_stx_transfer(&sender, 100).unwrap();
+        assets
+            .add_token_transfer(&sender, token.clone(), 5)
+            .unwrap();
+        assets.add_asset_transfer(&recipient, token.clone(), Value::UInt(7));
+
+        let err = Error::AbortedByCallback(Some(Value::Int(1)), assets, vec![]);
+        let (value, assets, events) = err.abort_details().unwrap();
+        assert_eq!(value, &Some(Value::Int(1)));
+        assert_eq!(assets.get_stx(&sender), Some(100));
+        assert_eq!(assets.get_fungible_tokens(&sender, &token), Some(5));
+        assert_eq!(
+            assets.get_nonfungible_tokens(&recipient, &token),
+            Some(&vec![Value::UInt(7)])
+        );
+        assert!(events.is_empty());
+
+        let json = err.into_json();
+        assert_eq!(json["reason"], "AbortedByCallback");
+        assert_eq!(json["reason_data"]["value"], "1");
+        let sender_assets = &json["reason_data"]["assets"][sender.to_string()];
+        assert_eq!(
+            sender_assets[AssetIdentifier::STX().to_string()]["amount"],
+            "100"
+        );
+        assert_eq!(sender_assets[token.to_string()]["amount"], "5");
+        assert_eq!(
+            json["reason_data"]["assets"][recipient.to_string()][token.to_string()]["values"],
+            json!(["u7"])
+        );
+        let summary = json["reason_data"]["asset_summary"].as_array().unwrap();
+        assert_eq!(summary.len(), 3);
+        assert!(summary.contains(&json!({
+            "principal": sender.to_string(),
+            "asset": token.to_string(),
+            "type": "ft",
+            "amount": "5",
+        })));
+
+        assert!(Error::BadTransaction("nope".into())
+            .abort_details()
+            .is_none());
+    }
+
     #[test]
     pub fn bad_syntax_test() {
         let marf = MarfedKV::temporary();
@@ -1668,6 +2053,95 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_cost_error_location() {
+        let marf = MarfedKV::temporary();
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
+        let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
+        let sender = StandardPrincipalData::transient().into();
+
+        clarity_instance
+            .begin_test_genesis_block(
+                &StacksBlockId::sentinel(),
+                &StacksBlockId([0 as u8; 32]),
+                &NULL_HEADER_DB,
+                &NULL_BURN_STATE_DB,
+            )
+            .commit_block();
+
+        {
+            let mut conn = clarity_instance.begin_block(
+                &StacksBlockId([0 as u8; 32]),
+                &StacksBlockId([1 as u8; 32]),
+                &NULL_HEADER_DB,
+                &NULL_BURN_STATE_DB,
+            );
+
+            let contract = "
+            (define-private (expand (list1 (list 10 int)))
+              (let ((list2 (concat list1 list1)))
+                (let ((list3 (concat list2 list2)))
+                  (let ((list4 (concat list3 list3)))
+                    (concat list4 list4)))))
+            (define-public (do-expand)
+              (ok (expand (list 1 2 3 4 5 6 7 8 9 10))))
+            ";
+
+            conn.as_transaction(|conn| {
+                let (ct_ast, ct_analysis) = conn
+                    .analyze_smart_contract(&contract_identifier, &contract)
+                    .unwrap();
+                conn.initialize_smart_contract(&contract_identifier, &ct_ast, &contract, |_, _| {
+                    false
+                })
+                .unwrap();
+                conn.save_analysis(&contract_identifier, &ct_analysis)
+                    .unwrap();
+            });
+
+            conn.commit_block();
+        }
+
+        clarity_instance.block_limit = ExecutionCost {
+            write_length: u64::max_value(),
+            write_count: u64::max_value(),
+            read_count: u64::max_value(),
+            read_length: u64::max_value(),
+            runtime: 1_000_000,
+        };
+
+        {
+            let mut conn = clarity_instance.begin_block(
+                &StacksBlockId([1 as u8; 32]),
+                &StacksBlockId([2 as u8; 32]),
+                &NULL_HEADER_DB,
+                &NULL_BURN_STATE_DB,
+            );
+            let err = conn
+                .as_transaction(|tx| {
+                    tx.run_contract_call(&sender, &contract_identifier, "do-expand", &[], |_, _| {
+                        false
+                    })
+                })
+                .unwrap_err();
+            match err {
+                Error::CostError(_, _, ref location) => {
+                    let location = location
+                        .as_ref()
+                        .expect("cost error should have a location");
+                    assert_eq!(location.contract_identifier, contract_identifier);
+                    assert_eq!(location.function_name.as_str(), "expand");
+                }
+                ref x => panic!("Expected a cost error, got {}", x),
+            }
+            assert!(format!("{}", err).ends_with(&format!(
+                "in function 'expand' of contract '{}'",
+                &contract_identifier
+            )));
+            conn.commit_block();
+        }
+    }
+
     #[test]
     pub fn test_block_limit() {
         let marf = MarfedKV::temporary();
@@ -1741,7 +2215,7 @@ mod tests {
                 ))
                 .unwrap_err()
             {
-                Error::CostError(total, limit) => {
+                Error::CostError(total, limit, _) => {
                     eprintln!("{}, {}", total, limit);
                     limit.runtime == 100 && total.runtime > 100
                 }
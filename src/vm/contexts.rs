@@ -31,9 +31,11 @@ use vm::database::{
     ClarityDatabase, DataMapMetadata, DataVariableMetadata, FungibleTokenMetadata,
     NonFungibleTokenMetadata,
 };
-use vm::errors::{CheckErrors, InterpreterError, InterpreterResult as Result, RuntimeErrorType};
+use vm::errors::{
+    CheckErrors, Error, InterpreterError, InterpreterResult as Result, RuntimeErrorType,
+};
 use vm::functions::handle_contract_call_special_cases;
-use vm::representations::{ClarityName, ContractName, SymbolicExpression};
+use vm::representations::{ClarityName, ContractName, Span, SymbolicExpression};
 use vm::stx_transfer_consolidated;
 use vm::types::signatures::FunctionSignature;
 use vm::types::{
@@ -79,6 +81,30 @@ pub enum AssetMapEntry {
     Asset(Vec<Value>),
 }
 
+/// The kind of asset in an `AssetMovement`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetMovementKind {
+    Stx,
+    StxBurn,
+    Ft,
+    Nft,
+}
+
+/// One entry of an `AssetMap` summary: a kind of asset that a principal sent (or burned).
+/// `amount` is a string, since it may not fit in a JSON number.  For non-fungible tokens,
+/// `amount` is the number of tokens sent, and `values` lists them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetMovement {
+    pub principal: String,
+    pub asset: String,
+    #[serde(rename = "type")]
+    pub kind: AssetMovementKind,
+    pub amount: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
 /**
 The AssetMap is used to track which assets have been transfered from whom
 during the execution of a transaction.
@@ -109,6 +135,25 @@ pub struct GlobalContext<'a> {
     read_only: Vec<bool>,
     pub cost_track: LimitedCostTracker,
     pub mainnet: bool,
+    /// Innermost user-defined function in which the cost budget was exceeded, if it was
+    cost_error_location: Option<CostErrorLocation>,
+}
+
+/// The user-defined function that was executing when a transaction exceeded its cost budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostErrorLocation {
+    pub contract_identifier: QualifiedContractIdentifier,
+    pub function_name: ClarityName,
+}
+
+impl fmt::Display for CostErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "function '{}' of contract '{}'",
+            self.function_name, self.contract_identifier
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -138,12 +183,36 @@ pub struct LocalContext<'a> {
 }
 
 pub struct CallStack {
-    stack: Vec<FunctionIdentifier>,
+    /// Each function being applied, and the span of the expression which applied it.  The
+    /// contract the span is in is only looked up if a stack trace is needed.
+    stack: Vec<(FunctionIdentifier, Option<Span>)>,
     set: HashSet<FunctionIdentifier>,
     apply_depth: usize,
 }
 
-pub type StackTrace = Vec<FunctionIdentifier>;
+/// A single entry of a stack trace: the function being applied, and, where
+/// available, the contract and source span of the expression that applied it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    pub function: FunctionIdentifier,
+    pub location: Option<(QualifiedContractIdentifier, Span)>,
+}
+
+pub type StackTrace = Vec<StackFrame>;
+
+impl fmt::Display for StackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.function)?;
+        if let Some((ref contract_identifier, ref span)) = self.location {
+            write!(
+                f,
+                " ({}, line {}, column {})",
+                contract_identifier, span.start_line, span.start_column
+            )?;
+        }
+        Ok(())
+    }
+}
 
 pub const TRANSIENT_CONTRACT_NAME: &str = "__transient";
 
@@ -408,6 +477,72 @@ impl AssetMap {
     }
 }
 
+impl AssetMap {
+    /// A flat list of the assets moved, one entry per principal and asset, sorted by principal
+    /// and then by asset.
+    pub fn summary(&self) -> Vec<AssetMovement> {
+        let mut movements = vec![];
+        for (principal, assets) in self.clone().to_table().into_iter() {
+            for (asset, entry) in assets.into_iter() {
+                let (kind, amount, values) = match entry {
+                    AssetMapEntry::STX(amount) => (AssetMovementKind::Stx, amount, vec![]),
+                    AssetMapEntry::Burn(amount) => (AssetMovementKind::StxBurn, amount, vec![]),
+                    AssetMapEntry::Token(amount) => (AssetMovementKind::Ft, amount, vec![]),
+                    AssetMapEntry::Asset(values) => (
+                        AssetMovementKind::Nft,
+                        values.len() as u128,
+                        values.iter().map(|v| v.to_string()).collect(),
+                    ),
+                };
+                movements.push(AssetMovement {
+                    principal: principal.to_string(),
+                    asset: asset.to_string(),
+                    kind,
+                    amount: format!("{}", amount),
+                    values,
+                });
+            }
+        }
+        movements.sort_by(|a, b| (&a.principal, &a.asset).cmp(&(&b.principal, &b.asset)));
+        movements
+    }
+
+    /// JSON representation of the assets moved, keyed by principal and then by asset.
+    /// Amounts are encoded as strings, since they may not fit in a JSON number.
+    pub fn json_serialize(&self) -> serde_json::Value {
+        let mut principals = serde_json::Map::new();
+        for (principal, assets) in self.clone().to_table().into_iter() {
+            let mut assets_json = serde_json::Map::new();
+            for (asset, entry) in assets.into_iter() {
+                let entry_json = match entry {
+                    AssetMapEntry::STX(amount) => json!({
+                        "type": "stx",
+                        "amount": format!("{}", amount),
+                    }),
+                    AssetMapEntry::Burn(amount) => json!({
+                        "type": "stx_burn",
+                        "amount": format!("{}", amount),
+                    }),
+                    AssetMapEntry::Token(amount) => json!({
+                        "type": "ft",
+                        "amount": format!("{}", amount),
+                    }),
+                    AssetMapEntry::Asset(values) => json!({
+                        "type": "nft",
+                        "values": values.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+                    }),
+                };
+                assets_json.insert(asset.to_string(), entry_json);
+            }
+            principals.insert(
+                principal.to_string(),
+                serde_json::Value::Object(assets_json),
+            );
+        }
+        serde_json::Value::Object(principals)
+    }
+}
+
 impl fmt::Display for AssetMap {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "[")?;
@@ -504,6 +639,7 @@ impl<'a> OwnedEnvironment<'a> {
     {
         assert!(self.context.is_top_level());
         self.begin();
+        self.context.cost_error_location = None;
 
         let result = {
             let mut exec_env = self.get_exec_environment(Some(sender));
@@ -647,6 +783,12 @@ impl<'a> OwnedEnvironment<'a> {
     pub fn destruct(self) -> Option<(ClarityDatabase<'a>, LimitedCostTracker)> {
         self.context.destruct()
     }
+
+    /// Where the last transaction run in this environment exceeded its cost budget, if it did so
+    /// inside a user-defined function.
+    pub fn cost_error_location(&self) -> Option<&CostErrorLocation> {
+        self.context.cost_error_location.as_ref()
+    }
 }
 
 impl CostTracker for Environment<'_, '_> {
@@ -885,6 +1027,7 @@ impl<'a, 'b> Environment<'a, 'b> {
             }
             self.call_stack.insert(&func_identifier, true);
             let res = self.execute_function_as_transaction(&func, &args, Some(&contract.contract_context));
+            self.global_context.record_cost_error_location(&res, contract_identifier, func.get_name());
             self.call_stack.remove(&func_identifier, true)?;
 
             match res {
@@ -1278,6 +1421,30 @@ impl<'a> GlobalContext<'a> {
             asset_maps: Vec::new(),
             event_batches: Vec::new(),
             mainnet,
+            cost_error_location: None,
+        }
+    }
+
+    /// If `result` is an error from exceeding the cost budget, and no location has been recorded
+    /// for it yet, record that it happened in `function_name` of `contract_identifier`.  Since
+    /// errors propagate outwards, the first location recorded is the innermost function.
+    pub fn record_cost_error_location<T>(
+        &mut self,
+        result: &Result<T>,
+        contract_identifier: &QualifiedContractIdentifier,
+        function_name: &ClarityName,
+    ) {
+        match result {
+            Err(Error::Unchecked(CheckErrors::CostBalanceExceeded(..)))
+            | Err(Error::Unchecked(CheckErrors::CostOverflow)) => {
+                if self.cost_error_location.is_none() {
+                    self.cost_error_location = Some(CostErrorLocation {
+                        contract_identifier: contract_identifier.clone(),
+                        function_name: function_name.clone(),
+                    });
+                }
+            }
+            _ => {}
         }
     }
 
@@ -1558,7 +1725,23 @@ impl CallStack {
     }
 
     pub fn insert(&mut self, function: &FunctionIdentifier, track: bool) {
-        self.stack.push(function.clone());
+        self.insert_at(function, track, None)
+    }
+
+    /// Push a function on the call stack, recording the span of the expression
+    /// which applied it. Expressions without source positions (i.e., with a
+    /// zero span) are recorded without a location.
+    pub fn insert_at(
+        &mut self,
+        function: &FunctionIdentifier,
+        track: bool,
+        call_site: Option<&Span>,
+    ) {
+        let call_site = match call_site {
+            Some(span) if *span != Span::zero() => Some(span.clone()),
+            _ => None,
+        };
+        self.stack.push((function.clone(), call_site));
         if track {
             self.set.insert(function.clone());
         }
@@ -1573,7 +1756,7 @@ impl CallStack {
     }
 
     pub fn remove(&mut self, function: &FunctionIdentifier, tracked: bool) -> Result<()> {
-        if let Some(removed) = self.stack.pop() {
+        if let Some((removed, _)) = self.stack.pop() {
             if removed != *function {
                 return Err(InterpreterError::InterpreterError(
                     "Tried to remove item from empty call stack.".to_string(),
@@ -1592,20 +1775,109 @@ impl CallStack {
         }
     }
 
+    /// Make a stack trace of the functions on the call stack.  The frames have no locations
+    /// yet; `locate_top_frame()` fills them in as the error unwinds the stack.
     #[cfg(feature = "developer-mode")]
     pub fn make_stack_trace(&self) -> StackTrace {
-        self.stack.clone()
+        self.stack
+            .iter()
+            .map(|(function, _)| StackFrame {
+                function: function.clone(),
+                location: None,
+            })
+            .collect()
     }
 
     #[cfg(not(feature = "developer-mode"))]
     pub fn make_stack_trace(&self) -> StackTrace {
         Vec::new()
     }
+
+    /// Fill in the location of the frame at the top of the call stack in `trace`, which was
+    /// made by `make_stack_trace()` at this depth or deeper.  `contract_identifier` is the
+    /// contract whose code applied the top function.
+    pub fn locate_top_frame(
+        &self,
+        trace: &mut StackTrace,
+        contract_identifier: &QualifiedContractIdentifier,
+    ) {
+        let (function, call_site) = match self.stack.last() {
+            Some((function, Some(call_site))) => (function, call_site),
+            _ => return,
+        };
+        if let Some(frame) = trace.get_mut(self.stack.len() - 1) {
+            if frame.function == *function && frame.location.is_none() {
+                frame.location = Some((contract_identifier.clone(), call_site.clone()));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use vm::types::StandardPrincipalData;
+
+    #[test]
+    fn test_asset_map_summary() {
+        let contract_id = QualifiedContractIdentifier::local("tokens").unwrap();
+        let p1 = PrincipalData::from(StandardPrincipalData(1, [1; 20]));
+        let p2 = PrincipalData::Contract(contract_id.clone());
+        let token = AssetIdentifier {
+            contract_identifier: contract_id.clone(),
+            asset_name: "gold".into(),
+        };
+
+        let mut assets = AssetMap::new();
+        assert!(assets.summary().is_empty());
+
+        assets.add_stx_transfer(&p1, 100).unwrap();
+        assets.add_stx_transfer(&p1, 20).unwrap();
+        assets.add_token_transfer(&p1, token.clone(), 5).unwrap();
+        assets.add_asset_transfer(&p2, token.clone(), Value::UInt(7));
+        assets.add_asset_transfer(&p2, token.clone(), Value::UInt(8));
+        assets.add_stx_burn(&p2, 3).unwrap();
+
+        let summary = assets.summary();
+        let find = |principal: &PrincipalData, asset: &AssetIdentifier| {
+            summary
+                .iter()
+                .find(|m| m.principal == principal.to_string() && m.asset == asset.to_string())
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(summary.len(), 4);
+
+        let stx = find(&p1, &AssetIdentifier::STX());
+        assert_eq!(stx.kind, AssetMovementKind::Stx);
+        assert_eq!(stx.amount, "120");
+        assert!(stx.values.is_empty());
+
+        let ft = find(&p1, &token);
+        assert_eq!(ft.kind, AssetMovementKind::Ft);
+        assert_eq!(ft.amount, "5");
+
+        let nft = find(&p2, &token);
+        assert_eq!(nft.kind, AssetMovementKind::Nft);
+        assert_eq!(nft.amount, "2");
+        assert_eq!(nft.values, vec!["u7".to_string(), "u8".to_string()]);
+
+        let burn = find(&p2, &AssetIdentifier::STX_burned());
+        assert_eq!(burn.kind, AssetMovementKind::StxBurn);
+        assert_eq!(burn.amount, "3");
+
+        // deterministic order
+        let mut sorted = summary.clone();
+        sorted.sort_by(|a, b| (&a.principal, &a.asset).cmp(&(&b.principal, &b.asset)));
+        assert_eq!(summary, sorted);
+
+        // serializes flat, with the kind as "type"
+        let json = serde_json::to_value(&nft).unwrap();
+        assert_eq!(json["type"], "nft");
+        assert_eq!(json["principal"], p2.to_string());
+        assert_eq!(json["values"], json!(["u7", "u8"]));
+        assert!(serde_json::to_value(&stx).unwrap().get("values").is_none());
+    }
 
     #[test]
     fn test_asset_map_abort() {
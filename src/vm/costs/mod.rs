@@ -280,6 +280,33 @@ pub enum CostErrors {
     CostContractLoadFailure,
 }
 
+impl CostErrors {
+    /// The execution cost that was spent, if this error is from exceeding the cost budget.
+    pub fn spent(&self) -> Option<ExecutionCost> {
+        match self {
+            CostErrors::CostBalanceExceeded(spent, _) => Some(spent.clone()),
+            _ => None,
+        }
+    }
+
+    /// The execution cost budget, if this error is from exceeding it.
+    pub fn budget(&self) -> Option<ExecutionCost> {
+        match self {
+            CostErrors::CostBalanceExceeded(_, budget) => Some(budget.clone()),
+            _ => None,
+        }
+    }
+
+    /// The memory used and the memory limit, if this error is from exceeding the memory limit.
+    /// (Memory is not a dimension of `ExecutionCost`, so `spent()` and `budget()` don't report it.)
+    pub fn memory_usage(&self) -> Option<(u64, u64)> {
+        match self {
+            CostErrors::MemoryBalanceExceeded(used, limit) => Some((*used, *limit)),
+            _ => None,
+        }
+    }
+}
+
 fn load_state_summary(mainnet: bool, clarity_db: &mut ClarityDatabase) -> Result<CostStateSummary> {
     let cost_voting_contract = boot_code_id("cost-voting", mainnet);
 
@@ -547,6 +547,18 @@ impl<'a> ClarityDatabase<'a> {
         Ok(data_size + contract_size)
     }
 
+    /// Get the length of the contract's source code, without loading the contract itself.
+    pub fn get_contract_src_size(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+    ) -> Result<u64> {
+        let key = ClarityDatabase::make_metadata_key(StoreType::Contract, "contract-size");
+        let contract_size: u64 = self.fetch_metadata(contract_identifier, &key)?.expect(
+            "Failed to read non-consensus contract metadata, even though contract exists in MARF.",
+        );
+        Ok(contract_size)
+    }
+
     /// used for adding the memory usage of `define-constant` variables.
     pub fn set_contract_data_size(
         &mut self,
@@ -18,10 +18,12 @@ use std::fmt;
 use vm::representations::Span;
 
 /// In a near future, we can go further in our static analysis and provide different levels
-/// of diagnostics, such as warnings, hints, best practices, etc.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// of diagnostics, such as hints, best practices, etc.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Level {
     Error,
+    /// The contract is valid, but probably not what the author intended
+    Warning,
 }
 
 pub trait DiagnosableError {
@@ -29,7 +31,7 @@ pub trait DiagnosableError {
     fn suggestion(&self) -> Option<String>;
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Diagnostic {
     pub level: Level,
     pub message: String,
@@ -47,6 +49,15 @@ impl Diagnostic {
         }
     }
 
+    pub fn warn(error: &dyn DiagnosableError) -> Diagnostic {
+        Diagnostic {
+            spans: vec![],
+            level: Level::Warning,
+            message: error.message(),
+            suggestion: error.suggestion(),
+        }
+    }
+
     pub fn add_span(&mut self, start_line: u32, start_column: u32, end_line: u32, end_column: u32) {
         self.spans.push(Span {
             start_line,
@@ -81,3 +92,30 @@ impl fmt::Display for Diagnostic {
         write!(f, "\n")
     }
 }
+
+/// A compact, serializable summary of a Clarity parse or analysis error, for shipping to tooling
+/// that does not need (or cannot deserialize) the expressions the error refers to.
+/// `code` is the name of the error variant, and each span is
+/// (start_line, start_column, end_line, end_column).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ErrorSummary {
+    pub code: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+    pub spans: Vec<(u32, u32, u32, u32)>,
+}
+
+impl ErrorSummary {
+    pub fn new(code: &str, diagnostic: &Diagnostic) -> ErrorSummary {
+        ErrorSummary {
+            code: code.to_string(),
+            message: diagnostic.message.clone(),
+            suggestion: diagnostic.suggestion.clone(),
+            spans: diagnostic
+                .spans
+                .iter()
+                .map(|s| (s.start_line, s.start_column, s.end_line, s.end_column))
+                .collect(),
+        }
+    }
+}
@@ -147,7 +147,30 @@ impl fmt::Display for Error {
 
 impl fmt::Display for RuntimeErrorType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            RuntimeErrorType::UnknownBlockHeaderHash(ref hash) => write!(
+                f,
+                "UnknownBlockHeaderHash: block {} is not known to this node",
+                hash.to_hex()
+            ),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl RuntimeErrorType {
+    /// Is this an arithmetic error -- i.e. an overflow, underflow, division by zero, or token
+    /// supply over/underflow?
+    pub fn is_arithmetic(&self) -> bool {
+        match self {
+            RuntimeErrorType::Arithmetic(_)
+            | RuntimeErrorType::ArithmeticOverflow
+            | RuntimeErrorType::ArithmeticUnderflow
+            | RuntimeErrorType::DivisionByZero
+            | RuntimeErrorType::SupplyOverflow(..)
+            | RuntimeErrorType::SupplyUnderflow(..) => true,
+            _ => false,
+        }
     }
 }
 
@@ -222,19 +245,111 @@ impl Into<Value> for ShortReturnType {
 #[cfg(test)]
 mod test {
     use super::*;
+    use vm::contexts::CallStack;
     use vm::execute;
+    use vm::functions::lookup_reserved_functions;
+    use vm::representations::Span;
+    use vm::tests::with_memory_environment;
+    use vm::types::QualifiedContractIdentifier;
 
     #[test]
     fn error_formats() {
         let t = "(/ 10 0)";
         let expected = "DivisionByZero
  Stack Trace: 
-_native_:native_div
+_native_:native_div (S1G2081040G2081040G2081040G208105NK8PE5.__transient, line 1, column 1)
 ";
 
         assert_eq!(format!("{}", execute(t).unwrap_err()), expected);
     }
 
+    #[test]
+    fn error_formats_without_spans() {
+        let mut call_stack = CallStack::new();
+        let identifier = lookup_reserved_functions("/").unwrap().get_identifier();
+        call_stack.insert(&identifier, false);
+        call_stack.insert_at(&identifier, false, Some(&Span::zero()));
+
+        let err = Error::Runtime(
+            RuntimeErrorType::DivisionByZero,
+            Some(call_stack.make_stack_trace()),
+        );
+        let expected = "DivisionByZero
+ Stack Trace: 
+_native_:native_div
+_native_:native_div
+";
+        assert_eq!(format!("{}", err), expected);
+    }
+
+    #[test]
+    fn error_formats_contract_call() {
+        let contract_a = "(define-read-only (divide (x int))
+  (/ x 0))";
+        let contract_b = "(define-read-only (call-divide)
+  (contract-call? .contract-a divide 1))";
+        let contract_a_id = QualifiedContractIdentifier::local("contract-a").unwrap();
+        let contract_b_id = QualifiedContractIdentifier::local("contract-b").unwrap();
+
+        with_memory_environment(
+            |owned_env| {
+                owned_env
+                    .initialize_contract(contract_a_id.clone(), contract_a)
+                    .unwrap();
+                owned_env
+                    .initialize_contract(contract_b_id.clone(), contract_b)
+                    .unwrap();
+
+                let err = owned_env
+                    .execute_transaction(
+                        Value::from(contract_b_id.issuer.clone()),
+                        contract_b_id.clone(),
+                        "call-divide",
+                        &[],
+                    )
+                    .unwrap_err();
+
+                let expected = format!(
+                    "DivisionByZero
+ Stack Trace: 
+{b}:call-divide
+_native_:special_contract-call ({b}, line 2, column 3)
+{a}:divide
+_native_:native_div ({a}, line 2, column 3)
+",
+                    a = contract_a_id,
+                    b = contract_b_id
+                );
+                assert_eq!(format!("{}", err), expected);
+            },
+            true,
+        );
+    }
+
+    #[test]
+    fn unknown_block_header_hash_formats() {
+        let hash = BlockHeaderHash([0xab; 32]);
+        let msg = format!("{}", RuntimeErrorType::UnknownBlockHeaderHash(hash.clone()));
+        assert!(msg.contains(&hash.to_hex()), "{}", msg);
+        assert!(msg.contains("not known to this node"), "{}", msg);
+    }
+
+    #[test]
+    fn runtime_error_is_arithmetic() {
+        let arithmetic = vec![
+            RuntimeErrorType::Arithmetic("bad".to_string()),
+            RuntimeErrorType::ArithmeticOverflow,
+            RuntimeErrorType::ArithmeticUnderflow,
+            RuntimeErrorType::DivisionByZero,
+            RuntimeErrorType::SupplyOverflow(2, 1),
+            RuntimeErrorType::SupplyUnderflow(1, 2),
+        ];
+        for err in arithmetic.iter() {
+            assert!(err.is_arithmetic(), "{:?}", err);
+        }
+        assert!(!RuntimeErrorType::NoSuchToken.is_arithmetic());
+    }
+
     #[test]
     fn equality() {
         assert_eq!(
@@ -65,6 +65,7 @@ use std::convert::{TryFrom, TryInto};
 pub use vm::contexts::MAX_CONTEXT_DEPTH;
 use vm::costs::cost_functions::ClarityCostFunction;
 pub use vm::functions::stx_transfer_consolidated;
+use vm::representations::Span;
 
 const MAX_CALL_STACK_DEPTH: usize = 64;
 
@@ -118,9 +119,9 @@ pub fn lookup_function(name: &str, env: &mut Environment) -> Result<CallableType
 
 fn add_stack_trace(result: &mut Result<Value>, env: &Environment) {
     if let Err(Error::Runtime(_, ref mut stack_trace)) = result {
-        if stack_trace.is_none() {
-            stack_trace.replace(env.call_stack.make_stack_trace());
-        }
+        let stack_trace = stack_trace.get_or_insert_with(|| env.call_stack.make_stack_trace());
+        env.call_stack
+            .locate_top_frame(stack_trace, &env.contract_context.contract_identifier);
     }
 }
 
@@ -129,6 +130,18 @@ pub fn apply(
     args: &[SymbolicExpression],
     env: &mut Environment,
     context: &LocalContext,
+) -> Result<Value> {
+    apply_at(function, args, env, context, None)
+}
+
+/// Apply `function` to `args`, recording `call_site` (the span of the applying
+/// expression in the current contract) in the call stack.
+fn apply_at(
+    function: &CallableType,
+    args: &[SymbolicExpression],
+    env: &mut Environment,
+    context: &LocalContext,
+    call_site: Option<&Span>,
 ) -> Result<Value> {
     let identifier = function.get_identifier();
     // Aaron: in non-debug executions, we shouldn't track a full call-stack.
@@ -149,7 +162,8 @@ pub fn apply(
     }
 
     if let CallableType::SpecialFunction(_, function) = function {
-        env.call_stack.insert(&identifier, track_recursion);
+        env.call_stack
+            .insert_at(&identifier, track_recursion, call_site);
         let mut resp = function(args, env, context);
         add_stack_trace(&mut resp, env);
         env.call_stack.remove(&identifier, track_recursion)?;
@@ -181,14 +195,23 @@ pub fn apply(
         }
         env.call_stack.decr_apply_depth();
 
-        env.call_stack.insert(&identifier, track_recursion);
+        env.call_stack
+            .insert_at(&identifier, track_recursion, call_site);
         let mut resp = match function {
             CallableType::NativeFunction(_, function, cost_function) => {
                 runtime_cost(*cost_function, env, evaluated_args.len())
                     .map_err(Error::from)
                     .and_then(|_| function.apply(evaluated_args))
             }
-            CallableType::UserFunction(function) => function.apply(&evaluated_args, env),
+            CallableType::UserFunction(function) => {
+                let resp = function.apply(&evaluated_args, env);
+                env.global_context.record_cost_error_location(
+                    &resp,
+                    &env.contract_context.contract_identifier,
+                    function.get_name(),
+                );
+                resp
+            }
             _ => panic!("Should be unreachable."),
         };
         add_stack_trace(&mut resp, env);
@@ -218,7 +241,7 @@ pub fn eval<'a>(
                 .match_atom()
                 .ok_or(CheckErrors::BadFunctionName)?;
             let f = lookup_function(&function_name, env)?;
-            apply(&f, &rest, env, context)
+            apply_at(&f, &rest, env, context, Some(&exp.span))
         }
         TraitReference(_, _) | Field(_) => unreachable!("can't be evaluated"),
     }
@@ -35,7 +35,7 @@ use chainstate::stacks::index::storage::TrieFileStorage;
 use chainstate::stacks::index::MarfTrieId;
 use chainstate::stacks::StacksBlockId;
 use vm::contexts::Environment;
-use vm::costs::{ClarityCostFunctionReference, ExecutionCost, LimitedCostTracker};
+use vm::costs::{ClarityCostFunctionReference, CostErrors, ExecutionCost, LimitedCostTracker};
 use vm::database::{
     ClarityDatabase, MarfedKV, MemoryBackingStore, NULL_BURN_STATE_DB, NULL_HEADER_DB,
 };
@@ -236,6 +236,46 @@ fn test_tracked_costs(prog: &str) -> ExecutionCost {
     tracker.get_total()
 }
 
+#[test]
+fn test_cost_errors_accessors() {
+    let spent = ExecutionCost {
+        write_length: 1,
+        write_count: 2,
+        read_length: 3,
+        read_count: 4,
+        runtime: 500,
+    };
+    let budget = ExecutionCost {
+        write_length: 10,
+        write_count: 20,
+        read_length: 30,
+        read_count: 40,
+        runtime: 50,
+    };
+
+    let err = CostErrors::CostBalanceExceeded(spent.clone(), budget.clone());
+    assert_eq!(err.spent(), Some(spent));
+    assert_eq!(err.budget(), Some(budget));
+    assert_eq!(err.memory_usage(), None);
+
+    let err = CostErrors::MemoryBalanceExceeded(200, 100);
+    assert_eq!(err.memory_usage(), Some((200, 100)));
+    assert_eq!(err.spent(), None);
+    assert_eq!(err.budget(), None);
+
+    for err in [
+        CostErrors::CostOverflow,
+        CostErrors::CostComputationFailed("failed".into()),
+        CostErrors::CostContractLoadFailure,
+    ]
+    .iter()
+    {
+        assert_eq!(err.spent(), None);
+        assert_eq!(err.budget(), None);
+        assert_eq!(err.memory_usage(), None);
+    }
+}
+
 #[test]
 fn test_all() {
     let baseline = test_tracked_costs("1");
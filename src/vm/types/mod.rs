@@ -1268,6 +1268,57 @@ impl fmt::Display for TupleData {
     }
 }
 
+/// Check that a value stays within the given bounds on its (estimated) total size in bytes and
+/// on its total number of elements.  Every value counts as one element, including the items of
+/// lists, the fields of tuples, and the inner values of optionals and responses.  The byte
+/// estimate uses each atom's type size, plus a wrapper byte for each container and the length of
+/// each tuple field name.
+pub fn check_value_limits(
+    value: &Value,
+    max_bytes: usize,
+    max_elements: usize,
+) -> std::result::Result<(), CheckErrors> {
+    let mut total_bytes = 0usize;
+    let mut total_elements = 0usize;
+    let mut stack = vec![value];
+
+    while let Some(value) = stack.pop() {
+        total_elements = total_elements.saturating_add(1);
+        let value_bytes = match value {
+            Value::Sequence(SequenceData::List(ref list)) => {
+                stack.extend(list.data.iter());
+                WRAPPER_VALUE_SIZE as usize
+            }
+            Value::Tuple(ref tuple) => {
+                let mut names_len = 0usize;
+                for (name, field) in tuple.data_map.iter() {
+                    names_len = names_len.saturating_add(name.len() as usize);
+                    stack.push(field);
+                }
+                names_len.saturating_add(WRAPPER_VALUE_SIZE as usize)
+            }
+            Value::Optional(OptionalData {
+                data: Some(ref inner),
+            }) => {
+                stack.push(inner);
+                WRAPPER_VALUE_SIZE as usize
+            }
+            Value::Response(ResponseData { ref data, .. }) => {
+                stack.push(data);
+                WRAPPER_VALUE_SIZE as usize
+            }
+            _ => value.size() as usize,
+        };
+        total_bytes = total_bytes.saturating_add(value_bytes);
+
+        if total_bytes > max_bytes || total_elements > max_elements {
+            return Err(CheckErrors::ValueTooLarge);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1447,4 +1498,35 @@ mod test {
         }));
         let _ = buff.expect_buff(4);
     }
+
+    #[test]
+    fn test_check_value_limits() {
+        let tuple = TupleData::from_data(vec![
+            ("a".into(), Value::Int(1)),
+            ("b".into(), Value::some(Value::UInt(2)).unwrap()),
+        ])
+        .unwrap();
+        let value = Value::list_from(vec![Value::from(tuple.clone()), Value::from(tuple)]).unwrap();
+
+        // list + 2 tuples + 4 fields + 2 inner uints
+        assert_eq!(check_value_limits(&value, 1024, 9), Ok(()));
+
+        // too many elements
+        assert_eq!(
+            check_value_limits(&value, 1024, 8),
+            Err(CheckErrors::ValueTooLarge)
+        );
+
+        // too many bytes (buffers carry a 4-byte length prefix)
+        let buff = Value::buff_from(vec![0; 100]).unwrap();
+        assert_eq!(check_value_limits(&buff, 104, 1), Ok(()));
+        assert_eq!(
+            check_value_limits(&buff, 103, 1),
+            Err(CheckErrors::ValueTooLarge)
+        );
+        assert_eq!(
+            check_value_limits(&value, 64, 100),
+            Err(CheckErrors::ValueTooLarge)
+        );
+    }
 }
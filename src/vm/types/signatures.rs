@@ -1338,6 +1338,81 @@ impl fmt::Display for FunctionArg {
     }
 }
 
+/// Describe the shape of a value of type `t` as a JSON Schema fragment, e.g. for validating
+/// contract-call arguments entered in a form.  Integers are string-encoded (since they may not
+/// fit in a JSON number), buffers are hex strings, tuples are objects with every field required,
+/// optionals are nullable, and responses are objects with exactly one of `ok` or `err`.
+pub fn type_signature_to_json_schema(t: &TypeSignature) -> serde_json::Value {
+    match t {
+        NoType => json!({}),
+        IntType => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        UIntType => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        BoolType => json!({ "type": "boolean" }),
+        PrincipalType => json!({
+            "type": "string",
+            "pattern": "^S[0-9A-Z]+(\\.[a-zA-Z][a-zA-Z0-9_-]*)?$"
+        }),
+        TraitReferenceType(trait_id) => json!({
+            "type": "string",
+            "pattern": "^S[0-9A-Z]+\\.[a-zA-Z][a-zA-Z0-9_-]*$",
+            "description": format!("a contract that implements {}", trait_id)
+        }),
+        SequenceType(SequenceSubtype::BufferType(len)) => json!({
+            "type": "string",
+            "pattern": format!("^([0-9a-fA-F]{{2}}){{0,{}}}$", u32::from(len))
+        }),
+        SequenceType(SequenceSubtype::StringType(StringSubtype::ASCII(len))) => json!({
+            "type": "string",
+            "maxLength": u32::from(len)
+        }),
+        SequenceType(SequenceSubtype::StringType(StringSubtype::UTF8(len))) => json!({
+            "type": "string",
+            "maxLength": u32::from(len)
+        }),
+        SequenceType(SequenceSubtype::ListType(list_data)) => json!({
+            "type": "array",
+            "items": type_signature_to_json_schema(list_data.get_list_item_type()),
+            "maxItems": list_data.get_max_len()
+        }),
+        TupleType(tuple_sig) => {
+            let mut properties = serde_json::Map::new();
+            let mut required = vec![];
+            for (name, field_type) in tuple_sig.get_type_map().iter() {
+                properties.insert(name.to_string(), type_signature_to_json_schema(field_type));
+                required.push(name.to_string());
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false
+            })
+        }
+        OptionalType(inner) => json!({
+            "anyOf": [{ "type": "null" }, type_signature_to_json_schema(inner)]
+        }),
+        ResponseType(inner) => {
+            let (ok_type, err_type) = (&inner.0, &inner.1);
+            json!({
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "properties": { "ok": type_signature_to_json_schema(ok_type) },
+                        "required": ["ok"],
+                        "additionalProperties": false
+                    },
+                    {
+                        "type": "object",
+                        "properties": { "err": type_signature_to_json_schema(err_type) },
+                        "required": ["err"],
+                        "additionalProperties": false
+                    }
+                ]
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::CheckErrors::*;
@@ -1350,6 +1425,55 @@ mod test {
         TypeSignature::parse_type_repr(expr, &mut ()).unwrap_err()
     }
 
+    fn parse_type(val: &str) -> TypeSignature {
+        use vm::ast::parse;
+        let expr = &parse(&QualifiedContractIdentifier::transient(), val).unwrap()[0];
+        TypeSignature::parse_type_repr(expr, &mut ()).unwrap()
+    }
+
+    #[test]
+    fn json_schema_of_tuple_with_list() {
+        let schema = type_signature_to_json_schema(&parse_type(
+            "(tuple (amounts (list 5 uint)) (memo (buff 2)) (ok bool))",
+        ));
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "amounts": {
+                        "type": "array",
+                        "items": { "type": "string", "pattern": "^[0-9]+$" },
+                        "maxItems": 5
+                    },
+                    "memo": { "type": "string", "pattern": "^([0-9a-fA-F]{2}){0,2}$" },
+                    "ok": { "type": "boolean" }
+                },
+                "required": ["amounts", "memo", "ok"],
+                "additionalProperties": false
+            })
+        );
+    }
+
+    #[test]
+    fn json_schema_of_optional() {
+        assert_eq!(
+            type_signature_to_json_schema(&parse_type("(optional int)")),
+            json!({
+                "anyOf": [
+                    { "type": "null" },
+                    { "type": "string", "pattern": "^-?[0-9]+$" }
+                ]
+            })
+        );
+        assert_eq!(
+            type_signature_to_json_schema(&parse_type("(optional (string-ascii 10))")),
+            json!({
+                "anyOf": [{ "type": "null" }, { "type": "string", "maxLength": 10 }]
+            })
+        );
+    }
+
     #[test]
     fn type_of_list_of_buffs() {
         let value = execute("(list \"abc\" \"abcde\")").unwrap().unwrap();